@@ -1,22 +1,37 @@
-use crate::data::BuffData;
+use crate::{combo_ui::Combo, data::BuffData};
 use arc_util::ui::{render, Ui};
-use arcdps::{
-    exports::{self, CoreColor},
-    imgui::{Selectable, StyleColor},
-};
+use arcdps::exports::{self, CoreColor};
+use log::warn;
+use std::borrow::Cow;
+
+/// Opens a web URL in the default browser.
+///
+/// Only `http` & `https` URLs are opened, other targets are ignored.
+pub fn open_url(url: &str) {
+    let url = url.trim();
+    let lower = url.to_lowercase();
+    if !lower.starts_with("https://") && !lower.starts_with("http://") {
+        warn!("Refusing to open non-web url \"{url}\"");
+    } else if let Err(err) = open::that(url) {
+        warn!("Failed to open url \"{url}\": {err}");
+    }
+}
 
 /// Renders a tooltip for a buff.
 pub fn render_buff_tooltip(ui: &Ui, buff: &BuffData) {
     if ui.is_item_hovered() {
-        ui.tooltip(|| {
-            match buff.rarity.color() {
-                Some(color) => ui.text_colored(color, &buff.name),
-                None => ui.text(&buff.name),
-            }
-            if !buff.stats.is_empty() {
-                ui.text(buff.stats.join("\n"));
-            }
-        });
+        ui.tooltip(|| render_buff_tooltip_contents(ui, buff));
+    }
+}
+
+/// Renders the tooltip contents for a buff.
+pub fn render_buff_tooltip_contents(ui: &Ui, buff: &BuffData) {
+    match buff.rarity.color() {
+        Some(color) => ui.text_colored(color, &buff.name),
+        None => ui.text(&buff.name),
+    }
+    if !buff.stats.is_empty() {
+        ui.text(buff.stats.join("\n"));
     }
 }
 
@@ -39,7 +54,7 @@ pub fn render_buff_context_menu(
                 ui.set_clipboard_text(name);
             }
             if ui.small_button("Open wiki") {
-                let _ = open::that(format!(
+                open_url(&format!(
                     "https://wiki-en.guildwars2.com/wiki/Special:Search/{name}"
                 ));
             }
@@ -86,6 +101,38 @@ pub fn render_util_context_menu(
     )
 }
 
+/// Creates a combo for buffs with search & grouping by rarity or stat category.
+///
+/// Items without buff data are listed first.
+pub fn buff_combo<'a, T>(
+    label: impl Into<String>,
+    item_label: impl Fn(&T) -> Cow<str> + 'a,
+    item_data: impl Fn(&T) -> Option<&'a BuffData> + Copy + 'a,
+) -> Combo<'a, T> {
+    Combo::new(label, item_label)
+        .color(move |item| item_data(item).and_then(|buff| buff.rarity.color()))
+        .tooltip(move |ui, item| {
+            if let Some(buff) = item_data(item) {
+                render_buff_tooltip_contents(ui, buff);
+            }
+        })
+        .search(move |item, search| {
+            item_data(item).map_or(false, |buff| {
+                buff.display.to_lowercase().contains(search)
+                    || buff
+                        .stats
+                        .iter()
+                        .any(|stat| stat.to_lowercase().contains(search))
+            })
+        })
+        .grouping("Rarity", move |item| {
+            item_data(item).map(|buff| (-(buff.rarity as i32), buff.rarity.name().into()))
+        })
+        .grouping("Stats", move |item| {
+            item_data(item).map(|buff| (0, buff.display.clone()))
+        })
+}
+
 /// Renders a combo for buffs.
 pub fn render_buff_combo<'b>(
     ui: &Ui,
@@ -99,31 +146,10 @@ pub fn render_buff_combo<'b>(
         .map(|buff| buff.name.clone())
         .unwrap_or_default();
 
-    // TODO: search?
-    let mut result = None;
-    if let Some(_token) = ui.begin_combo(label, preview) {
-        for entry in buffs {
-            let selected = entry.id == selected_id;
-
-            let style = entry
-                .rarity
-                .color()
-                .map(|color| ui.push_style_color(StyleColor::Text, color));
-            if Selectable::new(&entry.name).selected(selected).build(ui) {
-                result = Some(entry);
-            }
-            drop(style);
-
-            // handle focus
-            if selected {
-                ui.set_item_default_focus();
-            }
-
-            // tooltip
-            if ui.is_item_hovered() {
-                render_buff_tooltip(ui, entry);
-            }
-        }
-    }
-    result
+    buff_combo(
+        label.as_ref(),
+        |buff: &&BuffData| buff.name.as_str().into(),
+        |buff| Some(*buff),
+    )
+    .build(ui, preview, buffs, |buff| buff.id == selected_id)
 }
@@ -1,16 +1,48 @@
-use crate::data::{DIMINISHED, MALNOURISHED};
-use arcdps::Profession;
-use serde::{Deserialize, Serialize};
+use crate::{
+    data::{spec_name, DefinitionKind, Definitions, DIMINISHED, MALNOURISHED},
+    tracking::buff::BuffState,
+};
+use arcdps::{Profession, Specialization};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::cmp::Ordering;
 
 /// Build data.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct Build {
+    /// Id of a personal build, used for assignments.
+    pub id: u32,
+
     pub prof: Profession,
+
+    /// Elite specialization, any specialization of the profession if [`None`].
+    pub spec: Option<Specialization>,
+
     pub name: String,
     pub notes: String,
-    pub food: u32,
-    pub util: u32,
+
+    /// Long-form notes, for example gear or rotation.
+    pub details: String,
+
+    /// Named links, for example guide or gear pages.
+    pub links: Vec<BuildLink>,
+
+    /// Acceptable food buff ids in order of preference.
+    #[serde(deserialize_with = "deserialize_ids")]
+    pub food: Vec<u32>,
+
+    /// Acceptable utility buff ids in order of preference.
+    #[serde(deserialize_with = "deserialize_ids")]
+    pub util: Vec<u32>,
+
+    /// Required custom buff ids.
+    pub custom: Vec<u32>,
+
+    /// Tags for grouping & search.
+    pub tags: Vec<String>,
+
+    /// Build template chat link.
+    pub template: String,
 
     #[serde(skip)]
     pub visible: bool,
@@ -22,22 +54,122 @@ impl Build {
         prof: Profession,
         name: impl Into<String>,
         notes: impl Into<String>,
-        food: u32,
-        util: u32,
+        food: Vec<u32>,
+        util: Vec<u32>,
     ) -> Self {
         Self {
+            id: 0,
             prof,
+            spec: None,
             name: name.into(),
             notes: notes.into(),
+            details: String::new(),
+            links: Vec::new(),
             food,
             util,
+            custom: Vec::new(),
+            tags: Vec::new(),
+            template: String::new(),
             visible: true,
         }
     }
 
     /// Creates a new empty build.
     pub fn empty() -> Self {
-        Self::new(Profession::Unknown, "", "", MALNOURISHED, DIMINISHED)
+        Self::new(
+            Profession::Unknown,
+            "",
+            "",
+            vec![MALNOURISHED],
+            vec![DIMINISHED],
+        )
+    }
+
+    /// Returns the preferred food buff id.
+    pub fn primary_food(&self) -> Option<u32> {
+        Self::primary(&self.food, MALNOURISHED)
+    }
+
+    /// Returns the preferred utility buff id.
+    pub fn primary_util(&self) -> Option<u32> {
+        Self::primary(&self.util, DIMINISHED)
+    }
+
+    /// Returns the first buff id, ignoring the negative buff used as placeholder.
+    fn primary(ids: &[u32], negative: u32) -> Option<u32> {
+        ids.iter().copied().find(|id| *id != negative)
+    }
+
+    /// Checks the food state for compliance with the build.
+    pub fn food_compliance(&self, defs: &Definitions, state: BuffState<u32>) -> Compliance {
+        Compliance::check(defs, state, &self.food, MALNOURISHED)
+    }
+
+    /// Checks the utility state for compliance with the build.
+    pub fn util_compliance(&self, defs: &Definitions, state: BuffState<u32>) -> Compliance {
+        Compliance::check(defs, state, &self.util, DIMINISHED)
+    }
+
+    /// Checks whether the build is meant for the given profession & elite specialization.
+    pub fn matches_prof(&self, prof: Profession, spec: Specialization) -> bool {
+        self.prof == prof && self.spec.map_or(true, |build_spec| build_spec == spec)
+    }
+
+    /// Returns the name of the preferred food.
+    pub fn food_name<'d>(&self, defs: &'d Definitions) -> Option<&'d str> {
+        self.primary_food()
+            .and_then(|id| defs.definition(id))
+            .map(DefinitionKind::name)
+    }
+
+    /// Returns the name of the preferred utility.
+    pub fn util_name<'d>(&self, defs: &'d Definitions) -> Option<&'d str> {
+        self.primary_util()
+            .and_then(|id| defs.definition(id))
+            .map(DefinitionKind::name)
+    }
+
+    /// Checks whether the build matches the given lowercase search.
+    ///
+    /// Searches name, notes, details, tags, profession, specialization and food & utility names.
+    pub fn matches_search(&self, defs: &Definitions, search: &str) -> bool {
+        let prof: &str = self.prof.into();
+        let spec = self.spec.map(|spec| spec_name(self.prof, spec));
+        let buffs = self
+            .food
+            .iter()
+            .chain(&self.util)
+            .filter_map(|id| defs.definition(*id))
+            .map(DefinitionKind::name);
+
+        search.is_empty()
+            || [self.name.as_str(), &self.notes, &self.details, prof]
+                .into_iter()
+                .chain(spec)
+                .chain(self.tags.iter().map(String::as_str))
+                .chain(buffs)
+                .any(|text| text.to_lowercase().contains(search))
+    }
+
+    /// Checks whether the build matches the given profession, food & utility.
+    ///
+    /// Food & utility match by id or by their stat category.
+    pub fn is_active(
+        &self,
+        defs: &Definitions,
+        prof: Profession,
+        spec: Specialization,
+        food: BuffState<u32>,
+        util: BuffState<u32>,
+    ) -> bool {
+        let matches = |state, expected: &[u32], negative| match state {
+            BuffState::Some(id) => buff_matches(defs, id, expected, negative),
+            _ => false,
+        };
+
+        self.matches_prof(prof, spec)
+            && matches(food, &self.food, MALNOURISHED)
+            && matches(util, &self.util, DIMINISHED)
     }
 }
 
@@ -46,3 +178,193 @@ impl Default for Build {
         Self::empty()
     }
 }
+
+/// Checks whether the buff id matches any expected buff by id or stat category.
+///
+/// The negative buff used as placeholder never matches.
+fn buff_matches(defs: &Definitions, id: u32, expected: &[u32], negative: u32) -> bool {
+    let category = |id| {
+        defs.definition(id)
+            .and_then(DefinitionKind::data)
+            .map(|data| data.display.as_str())
+    };
+
+    id != negative
+        && expected
+            .iter()
+            .filter(|expected| **expected != negative)
+            .any(|expected| {
+                *expected == id || (category(id).is_some() && category(id) == category(*expected))
+            })
+}
+
+/// Named link of a [`Build`].
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BuildLink {
+    pub name: String,
+    pub url: String,
+}
+
+impl BuildLink {
+    /// Creates a new empty link.
+    pub const fn empty() -> Self {
+        Self {
+            name: String::new(),
+            url: String::new(),
+        }
+    }
+
+    /// Returns the display name, falling back to the url.
+    pub fn display_name(&self) -> &str {
+        if self.name.is_empty() {
+            &self.url
+        } else {
+            &self.name
+        }
+    }
+}
+
+/// Column to sort [`Build`]s by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BuildSorting {
+    Name,
+    Food,
+    Util,
+}
+
+impl BuildSorting {
+    /// Compares two builds according to the sorting.
+    ///
+    /// Builds without food or utility are sorted last.
+    pub fn compare(&self, defs: &Definitions, a: &Build, b: &Build) -> Ordering {
+        match self {
+            Self::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            Self::Food => Self::compare_names(a.food_name(defs), b.food_name(defs)),
+            Self::Util => Self::compare_names(a.util_name(defs), b.util_name(defs)),
+        }
+    }
+
+    /// Compares optional names, placing [`None`] last.
+    fn compare_names(a: Option<&str>, b: Option<&str>) -> Ordering {
+        match (a, b) {
+            (Some(a), Some(b)) => a.cmp(b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+    }
+}
+
+/// Compliance of a buff state with a [`Build`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Compliance {
+    /// Buff state is not known or no buff is required.
+    Unknown,
+
+    /// Acceptable buff is applied.
+    Matches,
+
+    /// Different buff is applied.
+    Wrong,
+
+    /// No buff or the negative buff is applied.
+    Missing,
+}
+
+impl Compliance {
+    /// Checks the buff state against the acceptable and negative buff ids.
+    ///
+    /// Buffs match the same way as for [`Build::is_active`].
+    fn check(defs: &Definitions, state: BuffState<u32>, expected: &[u32], negative: u32) -> Self {
+        let required = expected.iter().any(|id| *id != negative);
+        match state {
+            _ if !required => Self::Unknown,
+            BuffState::Unknown => Self::Unknown,
+            BuffState::None => Self::Missing,
+            BuffState::Some(id) if id == negative => Self::Missing,
+            BuffState::Some(id) if buff_matches(defs, id, expected, negative) => Self::Matches,
+            BuffState::Some(_) => Self::Wrong,
+        }
+    }
+}
+
+/// Buff ids data for backwards compatibility.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum BuffIdsData {
+    Single(u32),
+    Multiple(Vec<u32>),
+}
+
+impl From<BuffIdsData> for Vec<u32> {
+    fn from(data: BuffIdsData) -> Self {
+        match data {
+            BuffIdsData::Single(id) => vec![id],
+            BuffIdsData::Multiple(ids) => ids,
+        }
+    }
+}
+
+/// Deserializes buff ids from a list or a single legacy id.
+fn deserialize_ids<'de, D>(deserializer: D) -> Result<Vec<u32>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    BuffIdsData::deserialize(deserializer).map(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn legacy_ids() {
+        let build: Build =
+            serde_json::from_str(r#"{ "name": "Legacy", "food": 57244, "util": 9963 }"#).unwrap();
+        assert_eq!(build.food, [57244]);
+        assert_eq!(build.util, [9963]);
+    }
+
+    #[test]
+    fn multiple_ids() {
+        let build: Build = serde_json::from_str(
+            r#"{ "name": "Multiple", "food": [57244, 57051], "util": [9963] }"#,
+        )
+        .unwrap();
+        assert_eq!(build.food, [57244, 57051]);
+        assert_eq!(build.primary_food(), Some(57244));
+
+        let defs = Definitions::with_defaults();
+        assert_eq!(
+            build.food_compliance(&defs, BuffState::Some(57051)),
+            Compliance::Matches
+        );
+        assert_eq!(
+            build.food_compliance(&defs, BuffState::Some(57393)),
+            Compliance::Matches
+        );
+        assert_eq!(
+            build.food_compliance(&defs, BuffState::Some(MALNOURISHED)),
+            Compliance::Missing
+        );
+        assert_eq!(
+            build.food_compliance(&defs, BuffState::Some(1)),
+            Compliance::Wrong
+        );
+    }
+
+    #[test]
+    fn no_requirement() {
+        let defs = Definitions::with_defaults();
+        let build = Build::empty();
+        assert_eq!(
+            build.food_compliance(&defs, BuffState::None),
+            Compliance::Unknown
+        );
+        assert_eq!(
+            build.util_compliance(&defs, BuffState::Some(9963)),
+            Compliance::Unknown
+        );
+    }
+}
@@ -1,10 +1,27 @@
 mod build;
+pub mod share;
+pub mod spreadsheet;
+pub mod template;
 pub mod ui;
 
 pub use self::build::*;
 
+use self::{
+    share::{Conflict, ShareError},
+    spreadsheet::RowError,
+};
+use crate::{
+    data::{Definitions, LoadError},
+    util::parse_jsonc,
+};
 use arc_util::settings::HasSettings;
 use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs, io,
+    path::Path,
+};
+use strum::{AsRefStr, EnumIter};
 
 /// Component for user-defined builds.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -13,16 +30,51 @@ pub struct Builds {
     /// User-defined builds.
     pub entries: Vec<Build>,
 
+    /// Read-only builds from the build library file.
+    #[serde(skip)]
+    pub library: Vec<Build>,
+
+    /// Builds assigned to own characters by character name.
+    pub characters: BTreeMap<String, BuildRef>,
+
+    /// Builds assigned to squad members by account name.
+    pub accounts: BTreeMap<String, BuildRef>,
+
     /// Whether to display notes as table column.
     pub display_notes: bool,
 
     /// Whether to filter builds by profession.
     pub filter_prof: bool,
 
+    /// Grouping of builds.
+    pub grouping: Grouping,
+
+    /// Current sorting with whether it is reversed by group name.
+    ///
+    /// The table without grouping uses an empty name.
+    #[serde(skip)]
+    sorting: BTreeMap<String, (BuildSorting, bool)>,
+
+    /// Builds pending to be imported.
+    #[serde(skip)]
+    pending_import: Option<Result<Vec<Build>, ShareError>>,
+
+    /// Spreadsheet rows which could not be imported with their line number.
+    #[serde(skip)]
+    import_errors: Vec<(usize, RowError)>,
+
+    /// Builds selected for sharing.
+    #[serde(skip)]
+    selected: BTreeSet<BuildRef>,
+
     /// Current search contents.
     #[serde(skip)]
     search: String,
 
+    /// Whether build visibility needs to be refreshed.
+    #[serde(skip)]
+    search_outdated: bool,
+
     /// Edit mode.
     #[serde(skip)]
     edit: bool,
@@ -32,20 +84,212 @@ impl Builds {
     pub const fn new() -> Self {
         Self {
             entries: Vec::new(),
+            library: Vec::new(),
+            characters: BTreeMap::new(),
+            accounts: BTreeMap::new(),
             display_notes: true,
             filter_prof: false,
+            grouping: Grouping::None,
+            sorting: BTreeMap::new(),
+            pending_import: None,
+            import_errors: Vec::new(),
+            selected: BTreeSet::new(),
             search: String::new(),
+            search_outdated: false,
             edit: false,
         }
     }
 
-    /// Refreshes build visibility according to search.
-    fn refresh_search(&mut self) {
+    /// Attempts to load the build library from a given file.
+    pub fn try_load_library(&mut self, path: impl AsRef<Path>) -> Result<(), LoadError> {
+        // read file
+        let content = fs::read_to_string(path).map_err(|err| match err.kind() {
+            io::ErrorKind::NotFound => LoadError::NotFound,
+            _ => LoadError::FailedToRead,
+        })?;
+
+        // parse builds
+        let mut library: Vec<Build> = parse_jsonc(&content).ok_or(LoadError::InvalidJSON)?;
+        for build in &mut library {
+            build.visible = true;
+        }
+        self.library = library;
+        self.search_outdated = true;
+
+        Ok(())
+    }
+
+    /// Returns all builds with whether they are from the library.
+    ///
+    /// Library builds shadowed by a personal build with the same name are skipped.
+    pub fn all(&self) -> impl Iterator<Item = (&Build, bool)> {
+        self.entries.iter().map(|build| (build, false)).chain(
+            self.library
+                .iter()
+                .filter(|build| !self.is_personal(&build.name))
+                .map(|build| (build, true)),
+        )
+    }
+
+    /// Checks whether there is a personal build with the given name.
+    fn is_personal(&self, name: &str) -> bool {
+        self.entries.iter().any(|build| build.name == name)
+    }
+
+    /// Returns the reference used to assign a build.
+    pub fn reference(build: &Build, library: bool) -> BuildRef {
+        if library {
+            BuildRef::Library(build.name.clone())
+        } else {
+            BuildRef::Personal(build.id)
+        }
+    }
+
+    /// Returns the referenced build.
+    ///
+    /// Library references resolve to a personal build shadowing the library build, same as in [`Builds::all`].
+    pub fn find(&self, reference: &BuildRef) -> Option<&Build> {
+        match reference {
+            BuildRef::Personal(id) => self.entries.iter().find(|build| build.id == *id),
+            BuildRef::Library(name) => self
+                .entries
+                .iter()
+                .chain(&self.library)
+                .find(|build| build.name == *name),
+        }
+    }
+
+    /// Returns the build assigned to the character or its account.
+    ///
+    /// Builds assigned to the character take precedence.
+    pub fn assigned(&self, account: &str, character: &str) -> Option<&Build> {
+        self.characters
+            .get(character)
+            .or_else(|| self.accounts.get(account))
+            .and_then(|reference| self.find(reference))
+    }
+
+    /// Assigns a build to the character or removes the assignment.
+    pub fn assign_character(&mut self, character: impl Into<String>, build: Option<BuildRef>) {
+        Self::assign(&mut self.characters, character.into(), build)
+    }
+
+    /// Assigns a build to the account or removes the assignment.
+    pub fn assign_account(&mut self, account: impl Into<String>, build: Option<BuildRef>) {
+        Self::assign(&mut self.accounts, account.into(), build)
+    }
+
+    /// Helper to update an assignment map.
+    fn assign(map: &mut BTreeMap<String, BuildRef>, key: String, build: Option<BuildRef>) {
+        match build {
+            Some(build) => map.insert(key, build),
+            None => map.remove(&key),
+        };
+    }
+
+    /// Replaces assignments of a build with another build.
+    fn reassign(&mut self, old: &BuildRef, new: &BuildRef) {
+        for reference in self
+            .characters
+            .values_mut()
+            .chain(self.accounts.values_mut())
+        {
+            if reference == old {
+                *reference = new.clone();
+            }
+        }
+    }
+
+    /// Removes assignments of personal builds which no longer exist.
+    fn clean_assignments(&mut self) {
+        let entries = &self.entries;
+        let exists = |reference: &BuildRef| match reference {
+            BuildRef::Personal(id) => entries.iter().any(|build| build.id == *id),
+            BuildRef::Library(_) => true,
+        };
+        self.characters.retain(|_, reference| exists(reference));
+        self.accounts.retain(|_, reference| exists(reference));
+    }
+
+    /// Returns an id not used by any personal build.
+    fn next_id(&self) -> u32 {
+        self.entries.iter().map(|build| build.id).max().unwrap_or(0) + 1
+    }
+
+    /// Adds a new personal build.
+    pub fn add(&mut self, mut build: Build) -> BuildRef {
+        build.id = self.next_id();
+        build.visible = true;
+        let reference = BuildRef::Personal(build.id);
+        self.entries.push(build);
+        self.search_outdated = true;
+        reference
+    }
+
+    /// Ensures every personal build has a unique id.
+    fn ensure_ids(&mut self) {
+        let mut next_id = self.next_id();
+        let mut seen = BTreeSet::new();
         for build in &mut self.entries {
-            build.visible = self.search.is_empty()
-                || build.name.to_lowercase().contains(&self.search)
-                || build.notes.to_lowercase().contains(&self.search);
+            if build.id == 0 || !seen.insert(build.id) {
+                build.id = next_id;
+                next_id += 1;
+            }
+        }
+    }
+
+    /// Returns the amount of builds conflicting with existing builds by name.
+    pub fn conflicts(&self, builds: &[Build]) -> usize {
+        builds
+            .iter()
+            .filter(|build| self.is_personal(&build.name))
+            .count()
+    }
+
+    /// Imports builds, handling builds with the same name as existing ones.
+    pub fn import(&mut self, builds: Vec<Build>, conflict: Conflict) {
+        for mut build in builds {
+            build.visible = true;
+            match self
+                .entries
+                .iter()
+                .position(|entry| entry.name == build.name)
+            {
+                None => {
+                    self.add(build);
+                }
+                Some(index) => match conflict {
+                    Conflict::Replace => {
+                        // keep assignments of the replaced build
+                        build.id = self.entries[index].id;
+                        self.entries[index] = build;
+                    }
+                    Conflict::KeepBoth => {
+                        build.name = self.unique_name(&build.name);
+                        self.add(build);
+                    }
+                    Conflict::Skip => {}
+                },
+            }
+        }
+        self.search_outdated = true;
+    }
+
+    /// Returns a build name not used by any existing build.
+    fn unique_name(&self, name: &str) -> String {
+        (2..)
+            .map(|i| format!("{name} ({i})"))
+            .find(|name| !self.is_personal(name))
+            .unwrap()
+    }
+
+    /// Refreshes build visibility according to search.
+    fn refresh_search(&mut self, defs: &Definitions) {
+        let search = self.search.to_lowercase();
+        for build in self.entries.iter_mut().chain(&mut self.library) {
+            build.visible = build.matches_search(defs, &search);
         }
+        self.search_outdated = false;
     }
 }
 
@@ -55,6 +299,39 @@ impl Default for Builds {
     }
 }
 
+/// Reference to an assigned build.
+///
+/// Personal builds are referenced by their id to keep assignments when renamed.
+/// Read-only library builds are referenced by their name.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum BuildRef {
+    Personal(u32),
+    Library(String),
+}
+
+/// Grouping of builds in the builds tab.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    AsRefStr,
+    EnumIter,
+    Serialize,
+    Deserialize,
+)]
+pub enum Grouping {
+    #[default]
+    None,
+    Profession,
+    Tag,
+}
+
 impl HasSettings for Builds {
     type Settings = Builds;
 
@@ -66,7 +343,79 @@ impl HasSettings for Builds {
 
     fn load_settings(&mut self, loaded: Self::Settings) {
         self.entries = loaded.entries;
+        self.characters = loaded.characters;
+        self.accounts = loaded.accounts;
+        self.ensure_ids();
         self.display_notes = loaded.display_notes;
         self.filter_prof = loaded.filter_prof;
+        self.grouping = loaded.grouping;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arcdps::Profession;
+
+    fn build(name: &str) -> Build {
+        Build::new(Profession::Guardian, name, "", vec![57244], vec![9963])
+    }
+
+    #[test]
+    fn assignments() {
+        let mut builds = Builds::new();
+        let first = builds.add(build("Same"));
+        let second = builds.add(build("Same"));
+        assert_ne!(first, second);
+
+        builds.assign_character("Character", Some(second));
+        builds.assign_account("Account.1234", Some(first));
+        builds.entries[1].name = "Renamed".into();
+        assert_eq!(
+            builds
+                .assigned("Account.1234", "Character")
+                .map(|build| build.name.as_str()),
+            Some("Renamed")
+        );
+
+        builds.entries.remove(1);
+        builds.clean_assignments();
+        assert!(builds.characters.is_empty());
+        assert_eq!(
+            builds
+                .assigned("Account.1234", "Character")
+                .map(|build| build.id),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn library_shadowed() {
+        let mut builds = Builds::new();
+        builds.library = vec![build("Shared"), build("Library")];
+        builds.add(build("Shared"));
+
+        let shared = BuildRef::Library("Shared".into());
+        assert_eq!(builds.find(&shared).map(|build| build.id), Some(1));
+
+        let library = BuildRef::Library("Library".into());
+        assert_eq!(
+            builds.find(&library).map(|build| build.name.as_str()),
+            Some("Library")
+        );
+    }
+
+    #[test]
+    fn missing_ids() {
+        let mut builds = Builds::new();
+        builds.entries = vec![build("First"), build("Second"), build("Third")];
+        builds.entries[2].id = 5;
+        builds.ensure_ids();
+        let ids = builds
+            .entries
+            .iter()
+            .map(|build| build.id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids, [6, 7, 5]);
     }
 }
@@ -0,0 +1,143 @@
+use super::Build;
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+/// Prefix of build share codes.
+pub const SHARE_PREFIX: &str = "FRB";
+
+/// Current version of the share code format.
+pub const SHARE_VERSION: u32 = 1;
+
+/// Encodes builds as share code.
+///
+/// Share codes consist of the prefix & version followed by base64 encoded JSON.
+pub fn encode<'b>(builds: impl IntoIterator<Item = &'b Build>) -> String {
+    let builds = builds.into_iter().collect::<Vec<_>>();
+    let json = serde_json::to_vec(&builds).expect("failed to serialize builds");
+    format!("{SHARE_PREFIX}{SHARE_VERSION}:{}", STANDARD.encode(json))
+}
+
+/// Decodes builds from a share code.
+pub fn decode(code: &str) -> Result<Vec<Build>, ShareError> {
+    let (version, data) = code
+        .trim()
+        .strip_prefix(SHARE_PREFIX)
+        .and_then(|rest| rest.split_once(':'))
+        .ok_or(ShareError::InvalidPrefix)?;
+
+    match version.parse::<u32>() {
+        Ok(SHARE_VERSION) => {}
+        Ok(version) => return Err(ShareError::UnsupportedVersion(version)),
+        Err(_) => return Err(ShareError::InvalidPrefix),
+    }
+
+    let json = STANDARD
+        .decode(data.trim())
+        .map_err(|_| ShareError::InvalidEncoding)?;
+    let builds =
+        serde_json::from_slice::<Vec<Build>>(&json).map_err(|_| ShareError::InvalidJSON)?;
+
+    if builds.is_empty() {
+        Err(ShareError::Empty)
+    } else {
+        Ok(builds)
+    }
+}
+
+/// Error when decoding a share code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ShareError {
+    /// Code does not start with the share code prefix.
+    InvalidPrefix,
+
+    /// Code uses an unsupported format version.
+    UnsupportedVersion(u32),
+
+    /// Code contains invalid base64.
+    InvalidEncoding,
+
+    /// Code contains invalid build data.
+    InvalidJSON,
+
+    /// Code contains no builds.
+    Empty,
+}
+
+/// Handling of imported builds with the same name as existing builds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Conflict {
+    /// Replace the existing build.
+    Replace,
+
+    /// Keep both builds, renaming the imported build.
+    KeepBoth,
+
+    /// Skip the imported build.
+    Skip,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builds::Builds;
+    use arcdps::Profession;
+
+    fn build(name: &str) -> Build {
+        Build::new(Profession::Guardian, name, "notes", vec![57244], vec![9963])
+    }
+
+    #[test]
+    fn roundtrip() {
+        let builds = [build("First"), build("Second")];
+        let code = encode(&builds);
+        assert!(code.starts_with("FRB1:"));
+
+        let decoded = decode(&code).unwrap();
+        assert_eq!(decoded.len(), 2);
+        assert_eq!(decoded[0].name, "First");
+        assert_eq!(decoded[1].food, [57244]);
+    }
+
+    #[test]
+    fn malformed() {
+        assert_eq!(decode("garbage").unwrap_err(), ShareError::InvalidPrefix);
+        assert_eq!(decode("FRBx:abc").unwrap_err(), ShareError::InvalidPrefix);
+        assert_eq!(
+            decode("FRB2:abc").unwrap_err(),
+            ShareError::UnsupportedVersion(2)
+        );
+        assert_eq!(decode("FRB1:%%%").unwrap_err(), ShareError::InvalidEncoding);
+        assert_eq!(
+            decode(&format!("FRB1:{}", STANDARD.encode("{}"))).unwrap_err(),
+            ShareError::InvalidJSON
+        );
+        assert_eq!(
+            decode(&format!("FRB1:{}", STANDARD.encode("[]"))).unwrap_err(),
+            ShareError::Empty
+        );
+    }
+
+    #[test]
+    fn conflicts() {
+        let mut builds = Builds::new();
+        builds.entries.push(build("Existing"));
+
+        let imported = vec![build("Existing"), build("New")];
+        assert_eq!(builds.conflicts(&imported), 1);
+
+        builds.import(imported.clone(), Conflict::Skip);
+        assert_eq!(builds.entries.len(), 2);
+
+        builds.import(imported.clone(), Conflict::KeepBoth);
+        let names = builds
+            .entries
+            .iter()
+            .map(|build| build.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["Existing", "New", "Existing (2)", "New (2)"]);
+
+        builds.entries[0].notes.clear();
+        builds.import(vec![build("Existing")], Conflict::Replace);
+        assert_eq!(builds.entries.len(), 4);
+        assert_eq!(builds.entries[0].notes, "notes");
+    }
+}
@@ -0,0 +1,329 @@
+use super::Build;
+use crate::data::{elite_specs, BuffData, Definitions, DIMINISHED, MALNOURISHED, PROFESSIONS};
+use arcdps::{Profession, Specialization};
+use serde::{Deserialize, Serialize};
+
+/// Separator between multiple food or utility entries in a cell.
+pub const ENTRY_SEPARATOR: char = ';';
+
+/// Row of a builds spreadsheet.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Row {
+    profession: String,
+    name: String,
+
+    #[serde(default)]
+    notes: String,
+
+    #[serde(default)]
+    food: String,
+
+    #[serde(default, alias = "utility")]
+    util: String,
+}
+
+/// Exports builds as CSV.
+///
+/// Food & utility are written as names if known, otherwise as ids.
+/// The negative buffs used as placeholders are skipped.
+pub fn export<'b>(defs: &Definitions, builds: impl IntoIterator<Item = &'b Build>) -> String {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    for build in builds {
+        let profession = match build.spec {
+            Some(spec) => <&str>::from(spec),
+            None => build.prof.into(),
+        };
+        writer
+            .serialize(Row {
+                profession: profession.into(),
+                name: build.name.clone(),
+                notes: build.notes.clone(),
+                food: export_buffs(defs, &build.food, MALNOURISHED),
+                util: export_buffs(defs, &build.util, DIMINISHED),
+            })
+            .expect("failed to serialize build");
+    }
+    let data = writer.into_inner().expect("failed to write builds");
+    String::from_utf8(data).expect("builds csv is not utf8")
+}
+
+/// Helper to export buff ids as names.
+fn export_buffs(defs: &Definitions, ids: &[u32], negative: u32) -> String {
+    ids.iter()
+        .filter(|id| **id != negative)
+        .map(|id| match defs.definition(*id) {
+            Some(def) => def.name().to_string(),
+            None => id.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(&format!("{ENTRY_SEPARATOR} "))
+}
+
+/// Imports builds from CSV.
+///
+/// Tab separated values copied from a spreadsheet are accepted as well.
+/// Rows which can not be resolved are reported instead of imported.
+pub fn import(defs: &Definitions, data: &str) -> SheetImport {
+    let delimiter = match data.lines().next() {
+        Some(header) if header.contains('\t') && !header.contains(',') => b'\t',
+        _ => b',',
+    };
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(data.as_bytes());
+
+    // normalize header names
+    let headers = reader.headers().ok().map(|headers| {
+        headers
+            .iter()
+            .map(|header| header.to_lowercase())
+            .collect::<csv::StringRecord>()
+    });
+    if let Some(headers) = &headers {
+        reader.set_headers(headers.clone());
+    }
+
+    let mut result = SheetImport::default();
+    for record in reader.records() {
+        // quoted fields may span multiple lines, so use the record position
+        let (line, row) = match record {
+            Ok(record) => (
+                record.position().map(csv::Position::line),
+                record.deserialize::<Row>(headers.as_ref()),
+            ),
+            Err(err) => (err.position().map(csv::Position::line), Err(err)),
+        };
+        let line = line.unwrap_or_default() as usize;
+        let build = row
+            .map_err(|_| RowError::Malformed)
+            .and_then(|row| resolve_row(defs, row));
+        match build {
+            Ok(build) => result.builds.push(build),
+            Err(err) => result.errors.push((line, err)),
+        }
+    }
+    result
+}
+
+/// Resolves a spreadsheet row to a build.
+fn resolve_row(defs: &Definitions, row: Row) -> Result<Build, RowError> {
+    let (prof, spec) =
+        resolve_prof(&row.profession).ok_or(RowError::UnknownProfession(row.profession))?;
+    if row.name.is_empty() {
+        return Err(RowError::MissingName);
+    }
+    let food =
+        resolve_buffs(defs.all_food(), &row.food, MALNOURISHED).map_err(RowError::UnknownFood)?;
+    let util =
+        resolve_buffs(defs.all_util(), &row.util, DIMINISHED).map_err(RowError::UnknownUtil)?;
+
+    let mut build = Build::new(prof, row.name, row.notes, food, util);
+    build.spec = spec;
+    Ok(build)
+}
+
+/// Resolves a profession or elite specialization name.
+///
+/// An empty name resolves to any profession.
+fn resolve_prof(name: &str) -> Option<(Profession, Option<Specialization>)> {
+    if name.is_empty() {
+        return Some((Profession::Unknown, None));
+    }
+
+    PROFESSIONS.iter().find_map(|prof| {
+        if <&str>::from(*prof).eq_ignore_ascii_case(name) {
+            Some((*prof, None))
+        } else {
+            elite_specs(*prof)
+                .find(|spec| <&str>::from(*spec).eq_ignore_ascii_case(name))
+                .map(|spec| (*prof, Some(spec)))
+        }
+    })
+}
+
+/// Resolves separated buff ids or names.
+///
+/// Returns the first entry which could not be resolved as error.
+/// The negative buff used as placeholder is not accepted.
+fn resolve_buffs<'d>(
+    all: impl Iterator<Item = &'d BuffData> + Clone,
+    cell: &str,
+    negative: u32,
+) -> Result<Vec<u32>, String> {
+    let ids = cell
+        .split(ENTRY_SEPARATOR)
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let id = entry.parse::<u32>().ok();
+            all.clone()
+                .filter(|data| data.id != negative)
+                .find(|data| Some(data.id) == id || data.name.eq_ignore_ascii_case(entry))
+                .map(|data| data.id)
+                .ok_or_else(|| entry.to_string())
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if ids.is_empty() {
+        Err(String::new())
+    } else {
+        Ok(ids)
+    }
+}
+
+/// Result of a spreadsheet import.
+#[derive(Debug, Default, Clone)]
+pub struct SheetImport {
+    /// Successfully resolved builds.
+    pub builds: Vec<Build>,
+
+    /// Rows which could not be resolved with their line number.
+    pub errors: Vec<(usize, RowError)>,
+}
+
+/// Error when resolving a spreadsheet row.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RowError {
+    /// Row could not be read.
+    Malformed,
+
+    /// Profession or elite specialization is not known.
+    UnknownProfession(String),
+
+    /// Name is missing.
+    MissingName,
+
+    /// Food is missing or not known.
+    UnknownFood(String),
+
+    /// Utility is missing or not known.
+    UnknownUtil(String),
+}
+
+impl RowError {
+    /// Returns a description of the error.
+    pub fn description(&self) -> String {
+        match self {
+            Self::Malformed => "malformed row".into(),
+            Self::UnknownProfession(name) => format!("unknown profession \"{name}\""),
+            Self::MissingName => "missing name".into(),
+            Self::UnknownFood(name) if name.is_empty() => "missing food".into(),
+            Self::UnknownFood(name) => format!("unknown food \"{name}\""),
+            Self::UnknownUtil(name) if name.is_empty() => "missing utility".into(),
+            Self::UnknownUtil(name) => format!("unknown utility \"{name}\""),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::DefinitionKind;
+
+    fn name(defs: &Definitions, id: u32) -> &str {
+        defs.definition(id).map(DefinitionKind::name).unwrap()
+    }
+
+    #[test]
+    fn roundtrip() {
+        let defs = Definitions::with_defaults();
+        let mut build = Build::new(
+            Profession::Guardian,
+            "Heal FB",
+            "notes, with comma",
+            vec![57244, 57051],
+            vec![9963],
+        );
+        build.spec = Some(Specialization::Firebrand);
+
+        let data = export(&defs, [&build]);
+        let imported = import(&defs, &data);
+        assert!(imported.errors.is_empty());
+        assert_eq!(imported.builds.len(), 1);
+
+        let result = &imported.builds[0];
+        assert_eq!(result.prof, Profession::Guardian);
+        assert_eq!(result.spec, Some(Specialization::Firebrand));
+        assert_eq!(result.name, build.name);
+        assert_eq!(result.notes, build.notes);
+        assert_eq!(result.food, build.food);
+        assert_eq!(result.util, build.util);
+    }
+
+    #[test]
+    fn names_and_ids() {
+        let defs = Definitions::with_defaults();
+        let data = format!(
+            "Profession\tName\tNotes\tFood\tUtility\nrevenant\tAlac\t\t{}\t9963\n",
+            name(&defs, 57244).to_uppercase()
+        );
+
+        let imported = import(&defs, &data);
+        assert!(imported.errors.is_empty());
+        assert_eq!(imported.builds[0].prof, Profession::Revenant);
+        assert_eq!(imported.builds[0].food, [57244]);
+        assert_eq!(imported.builds[0].util, [9963]);
+    }
+
+    #[test]
+    fn unresolved() {
+        let defs = Definitions::with_defaults();
+        let data = "profession,name,notes,food,util\n\
+            Plumber,First,,57244,9963\n\
+            Guardian,Second,,Cake,9963\n\
+            Guardian,Third,,57244,\n\
+            Guardian,Fourth,,57244,9963\n";
+
+        let imported = import(&defs, data);
+        assert_eq!(imported.builds.len(), 1);
+        assert_eq!(imported.builds[0].name, "Fourth");
+        assert_eq!(
+            imported.errors,
+            [
+                (2, RowError::UnknownProfession("Plumber".into())),
+                (3, RowError::UnknownFood("Cake".into())),
+                (4, RowError::UnknownUtil(String::new())),
+            ]
+        );
+    }
+
+    #[test]
+    fn multiline_lines() {
+        let defs = Definitions::with_defaults();
+        let data = "profession,name,notes,food,util\n\
+            Guardian,First,\"multi\nline\nnotes\",57244,9963\n\
+            Plumber,Second,,57244,9963\n";
+
+        let imported = import(&defs, data);
+        assert_eq!(imported.builds[0].notes, "multi\nline\nnotes");
+        assert_eq!(
+            imported.errors,
+            [(5, RowError::UnknownProfession("Plumber".into()))]
+        );
+    }
+
+    #[test]
+    fn placeholders() {
+        let defs = Definitions::with_defaults();
+        let mut build = Build::empty();
+        build.name = "Empty".into();
+        build.food.push(57244);
+
+        let data = export(&defs, [&build]);
+        assert!(!data.contains(name(&defs, MALNOURISHED)));
+        assert!(!data.contains(name(&defs, DIMINISHED)));
+
+        let data = format!(
+            "profession,name,notes,food,util\nGuardian,Placeholder,,{MALNOURISHED},{}\n",
+            name(&defs, DIMINISHED)
+        );
+        let imported = import(&defs, &data);
+        assert!(imported.builds.is_empty());
+        assert_eq!(
+            imported.errors,
+            [(2, RowError::UnknownFood(MALNOURISHED.to_string()))]
+        );
+    }
+}
@@ -0,0 +1,156 @@
+use crate::data::{ELITE_SPECS, ELITE_SPEC_IDS, PROFESSION_CODES};
+use arcdps::{Profession, Specialization};
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+/// Header byte of build template chat links.
+const TEMPLATE_HEADER: u8 = 0x0D;
+
+/// Minimum length of decoded build template data.
+const TEMPLATE_LEN: usize = 44;
+
+/// Information decoded from a build template chat link.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Template {
+    /// Profession of the template.
+    pub prof: Profession,
+
+    /// Elite specialization of the template, if any is selected.
+    pub spec: Option<Specialization>,
+}
+
+/// Decodes a build template chat link.
+///
+/// The data consists of the header & profession code followed by 3 pairs of specialization id & traits.
+pub fn decode(link: &str) -> Result<Template, TemplateError> {
+    let data = link
+        .trim()
+        .strip_prefix("[&")
+        .and_then(|rest| rest.strip_suffix(']'))
+        .ok_or(TemplateError::InvalidLink)?;
+    let bytes = STANDARD
+        .decode(data)
+        .map_err(|_| TemplateError::InvalidEncoding)?;
+
+    if bytes.first() != Some(&TEMPLATE_HEADER) {
+        return Err(TemplateError::NotTemplate);
+    }
+    if bytes.len() < TEMPLATE_LEN {
+        return Err(TemplateError::TooShort);
+    }
+
+    let prof = PROFESSION_CODES
+        .iter()
+        .find(|(code, _)| *code == bytes[1])
+        .map(|(_, prof)| *prof)
+        .ok_or(TemplateError::UnknownProfession(bytes[1]))?;
+
+    // elite specialization is usually in the last slot
+    let spec = [bytes[6], bytes[4], bytes[2]].into_iter().find_map(|id| {
+        ELITE_SPEC_IDS
+            .iter()
+            .find(|(spec_id, _)| *spec_id == id)
+            .map(|(_, spec)| *spec)
+            .filter(|spec| {
+                ELITE_SPECS
+                    .iter()
+                    .any(|(elite, elite_prof, _)| elite == spec && *elite_prof == prof)
+            })
+    });
+
+    Ok(Template { prof, spec })
+}
+
+/// Error when decoding a build template chat link.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TemplateError {
+    /// Text is not a chat link.
+    InvalidLink,
+
+    /// Chat link contains invalid base64.
+    InvalidEncoding,
+
+    /// Chat link is not a build template.
+    NotTemplate,
+
+    /// Build template data is incomplete.
+    TooShort,
+
+    /// Build template contains an unknown profession code.
+    UnknownProfession(u8),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn elite() {
+        assert_eq!(
+            decode("[&DQEqGhArPjoAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=]"),
+            Ok(Template {
+                prof: Profession::Guardian,
+                spec: Some(Specialization::Firebrand)
+            })
+        );
+        assert_eq!(
+            decode(" [&DQkDGgwrPzoAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=] "),
+            Ok(Template {
+                prof: Profession::Revenant,
+                spec: Some(Specialization::Renegade)
+            })
+        );
+
+        // complete template with skills
+        assert_eq!(
+            decode("[&DQIEKRYqPTlwAHAAogD/ALgA/wB2AXUBAAAAAAAAAAAAAAAAAAAAAAAAAAA=]"),
+            Ok(Template {
+                prof: Profession::Warrior,
+                spec: Some(Specialization::Spellbreaker)
+            })
+        );
+    }
+
+    #[test]
+    fn core() {
+        assert_eq!(
+            decode("[&DQIEGgsrFjoAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=]"),
+            Ok(Template {
+                prof: Profession::Warrior,
+                spec: None
+            })
+        );
+
+        // complete template with skills
+        assert_eq!(
+            decode("[&DQIEKRYqMzlwAHAAogD/ALgA/wB2AXUBAAAAAAAAAAAAAAAAAAAAAAAAAAA=]"),
+            Ok(Template {
+                prof: Profession::Warrior,
+                spec: None
+            })
+        );
+    }
+
+    #[test]
+    fn legends() {
+        // revenant templates store legends & inactive legend skills after the skills
+        assert_eq!(
+            decode("[&DQkPGgMmNDncEdwRBhIGEisSKxLUEdQRyhHKEQEDAQMGEisS1BEGEisS1BE=]"),
+            Ok(Template {
+                prof: Profession::Revenant,
+                spec: Some(Specialization::Herald)
+            })
+        );
+    }
+
+    #[test]
+    fn malformed() {
+        assert_eq!(decode("DQEqGhAr"), Err(TemplateError::InvalidLink));
+        assert_eq!(decode("[&%%%]"), Err(TemplateError::InvalidEncoding));
+        assert_eq!(decode("[&AgEAAAAA]"), Err(TemplateError::NotTemplate));
+        assert_eq!(decode("[&DQEqGhAr]"), Err(TemplateError::TooShort));
+        assert_eq!(
+            decode("[&DQwqGhArPjoAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=]"),
+            Err(TemplateError::UnknownProfession(12))
+        );
+    }
+}
@@ -1,13 +1,23 @@
-use super::{build::Build, Builds};
+use super::{
+    build::{Build, BuildLink, BuildSorting},
+    share::{self, Conflict, ShareError},
+    spreadsheet,
+    template::{self, Template, TemplateError},
+    BuildRef, Builds, Grouping,
+};
 use crate::{
-    assets::{FOOD_ICON, UTIL_ICON},
+    assets::{FOOD_ICON, UNKNOWN_ICON, UTIL_ICON},
     buff_ui,
-    combo_ui::render_prof_select,
-    data::{DefinitionKind, Definitions},
-    tracking::buff::BuffState,
+    combo_ui::{render_combo, render_enum_combo, render_prof_select, Combo},
+    data::{
+        elite_specs, spec_name, BuffData, DefinitionKind, Definitions, DIMINISHED, MALNOURISHED,
+        PROFESSIONS,
+    },
+    reminder::custom::CustomReminder,
+    tracking::buff::{BuffState, Buffs},
 };
 use arc_util::{
-    colors::{GREEN, RED},
+    colors::{GREEN, GREY, RED},
     ui::{
         action::Action,
         render::{self, TableIconColumn},
@@ -16,137 +26,845 @@ use arc_util::{
 };
 use arcdps::{
     exports::{self, CoreColor},
-    imgui::TableFlags,
-    Profession,
+    imgui::{
+        StyleColor, TableBgTarget, TableColumnFlags, TableFlags, TableSortDirection, TreeNodeFlags,
+    },
+    Profession, Specialization,
 };
+use std::{collections::BTreeSet, iter};
+
+/// Width of long-form notes.
+const DETAILS_WIDTH: f32 = 400.0;
 
 pub type Props<'p> = (
     &'p Definitions,
-    Option<Profession>,
-    BuffState<u32>,
-    BuffState<u32>,
+    &'p [CustomReminder],
+    Option<Current<'p>>,
     bool,
 );
 
+/// Current character of the own player.
+#[derive(Debug, Clone, Copy)]
+pub struct Current<'p> {
+    pub account: &'p str,
+    pub character: &'p str,
+    pub prof: Profession,
+    pub spec: Specialization,
+    pub buffs: &'p Buffs,
+}
+
+/// Build shown in the view table.
+#[derive(Debug, Clone, Copy)]
+struct ViewEntry<'b> {
+    id: usize,
+    build: &'b Build,
+    library: bool,
+}
+
+/// Shared context for rendering view tables.
+#[derive(Debug, Clone, Copy)]
+struct TableContext<'a> {
+    defs: &'a Definitions,
+    custom: &'a [CustomReminder],
+    current: Option<Current<'a>>,
+    detect: Option<Current<'a>>,
+    selected: &'a BTreeSet<BuildRef>,
+    display_notes: bool,
+    show_icons: bool,
+}
+
+/// Action performed in the view tables.
+#[derive(Debug, Clone)]
+enum ViewAction {
+    None,
+    Assign(BuildRef),
+    Copy(Build),
+    Select(BuildRef, bool),
+}
+
 impl Builds {
     /// Renders viewing mode contents.
     fn render_view(
         &mut self,
         ui: &Ui,
         defs: &Definitions,
-        current_prof: Option<Profession>,
-        current_food: BuffState<u32>,
-        current_util: BuffState<u32>,
+        custom: &[CustomReminder],
+        current: Option<Current>,
         show_icons: bool,
     ) {
-        // render builds table
-        let build_column = TableIconColumn::new("Build", None);
-        let food_column = TableIconColumn::new("Food", FOOD_ICON.as_ref());
-        let util_column = TableIconColumn::new("Util", UTIL_ICON.as_ref());
-        let columns = if self.display_notes {
-            vec![
-                build_column,
-                TableIconColumn::new("Notes", None),
-                food_column,
-                util_column,
-            ]
-        } else {
-            vec![build_column, food_column, util_column]
+        // detect active builds if none is assigned
+        let detect =
+            current.filter(|current| self.assigned(current.account, current.character).is_none());
+
+        // collect shown builds
+        let mut shown = self
+            .all()
+            .enumerate()
+            .filter(|(_, (build, _))| self.is_shown(build, current))
+            .map(|(id, (build, library))| ViewEntry { id, build, library })
+            .collect::<Vec<_>>();
+
+        let context = TableContext {
+            defs,
+            custom,
+            current,
+            detect,
+            selected: &self.selected,
+            display_notes: self.display_notes,
+            show_icons,
         };
+        let mut action = ViewAction::None;
+        let mut changed_sorting = Vec::new();
+
+        match self.grouping {
+            Grouping::None => {
+                self.sort_group(defs, "", &mut shown);
+                if let Some(changed) =
+                    Self::render_table(ui, "##builds-table", &shown, &context, &mut action)
+                {
+                    changed_sorting.push((String::new(), changed));
+                }
+            }
+            Grouping::Profession => {
+                for prof in PROFESSIONS.iter().copied().chain([Profession::Unknown]) {
+                    let mut group = shown
+                        .iter()
+                        .filter(|entry| entry.build.prof == prof)
+                        .cloned()
+                        .collect::<Vec<_>>();
+                    let name = match prof {
+                        Profession::Unknown => "Any",
+                        prof => prof.into(),
+                    };
+                    self.sort_group(defs, name, &mut group);
+                    if let Some(changed) =
+                        Self::render_group(ui, name, &group, &context, &mut action)
+                    {
+                        changed_sorting.push((name.to_string(), changed));
+                    }
+                }
+            }
+            Grouping::Tag => {
+                let tags = shown
+                    .iter()
+                    .flat_map(|entry| &entry.build.tags)
+                    .map(String::as_str)
+                    .collect::<BTreeSet<_>>();
+                for tag in tags.into_iter().map(Some).chain([None]) {
+                    let mut group = shown
+                        .iter()
+                        .filter(|entry| match tag {
+                            Some(tag) => entry.build.tags.iter().any(|entry| entry == tag),
+                            None => entry.build.tags.is_empty(),
+                        })
+                        .cloned()
+                        .collect::<Vec<_>>();
+                    let name = tag.unwrap_or("Untagged");
+                    self.sort_group(defs, name, &mut group);
+                    if let Some(changed) =
+                        Self::render_group(ui, name, &group, &context, &mut action)
+                    {
+                        changed_sorting.push((name.to_string(), changed));
+                    }
+                }
+            }
+        }
+
+        // update sorting of each table
+        for (group, sorting) in changed_sorting {
+            match sorting {
+                Some(sorting) => self.sorting.insert(group, sorting),
+                None => self.sorting.remove(&group),
+            };
+        }
+
+        match action {
+            ViewAction::None => {}
+            ViewAction::Assign(reference) => {
+                if let Some(current) = current {
+                    self.assign_character(current.character, Some(reference));
+                }
+            }
+            ViewAction::Copy(build) => {
+                // shadow library build with a personal build
+                let library = Self::reference(&build, true);
+                let personal = self.add(build);
+                self.reassign(&library, &personal);
+            }
+            ViewAction::Select(reference, true) => {
+                self.selected.insert(reference);
+            }
+            ViewAction::Select(reference, false) => {
+                self.selected.remove(&reference);
+            }
+        }
+    }
+
+    /// Sorts the builds of a group according to the sorting of its table.
+    ///
+    /// Each table keeps its own sorting, as the table headers are sorted individually.
+    fn sort_group(&self, defs: &Definitions, group: &str, entries: &mut [ViewEntry]) {
+        if let Some((sorting, reverse)) = self.sorting.get(group) {
+            entries.sort_by(|a, b| sorting.compare(defs, a.build, b.build));
+            if *reverse {
+                entries.reverse();
+            }
+        }
+    }
+
+    /// Renders a collapsible group of builds.
+    ///
+    /// Returns the new sorting if changed.
+    fn render_group(
+        ui: &Ui,
+        name: &str,
+        entries: &[ViewEntry],
+        context: &TableContext,
+        action: &mut ViewAction,
+    ) -> Option<Option<(BuildSorting, bool)>> {
+        if entries.is_empty() {
+            return None;
+        }
 
+        let header = format!("{name} ({})##group-{name}", entries.len());
+        if ui.collapsing_header(header, TreeNodeFlags::DEFAULT_OPEN) {
+            let _id = ui.push_id(name);
+            Self::render_table(ui, "##builds-table", entries, context, action)
+        } else {
+            None
+        }
+    }
+
+    /// Renders a table of builds.
+    ///
+    /// Returns the new sorting if changed.
+    fn render_table(
+        ui: &Ui,
+        id: &str,
+        entries: &[ViewEntry],
+        context: &TableContext,
+        action: &mut ViewAction,
+    ) -> Option<Option<(BuildSorting, bool)>> {
+        let TableContext {
+            defs,
+            custom,
+            current,
+            detect,
+            selected,
+            display_notes,
+            show_icons,
+        } = *context;
+        let current_food = current.map_or(BuffState::Unknown, |current| current.buffs.food.state);
+        let current_util = current.map_or(BuffState::Unknown, |current| current.buffs.util.state);
+
+        // collect visible columns with their sorting
+        let mut columns = vec![
+            (
+                TableIconColumn::with_flags(
+                    "##select",
+                    None,
+                    TableColumnFlags::NO_SORT | TableColumnFlags::WIDTH_FIXED,
+                ),
+                None,
+            ),
+            (
+                TableIconColumn::new("Build", None),
+                Some(BuildSorting::Name),
+            ),
+        ];
+        if display_notes {
+            columns.push((
+                TableIconColumn::with_flags("Notes", None, TableColumnFlags::NO_SORT),
+                None,
+            ));
+        }
+        columns.push((
+            TableIconColumn::new("Food", FOOD_ICON.as_ref()),
+            Some(BuildSorting::Food),
+        ));
+        columns.push((
+            TableIconColumn::new("Util", UTIL_ICON.as_ref()),
+            Some(BuildSorting::Util),
+        ));
+        columns.push((
+            TableIconColumn::with_flags("Buffs", UNKNOWN_ICON.as_ref(), TableColumnFlags::NO_SORT),
+            None,
+        ));
+        let (columns, sortings): (Vec<_>, Vec<_>) = columns.into_iter().unzip();
+
+        let mut sorting = None;
         if let Some(_table) = render::table_with_icons(
             ui,
-            "##builds-table",
+            id,
             &columns,
-            TableFlags::SIZING_STRETCH_PROP | TableFlags::PAD_OUTER_X,
+            TableFlags::SIZING_STRETCH_PROP
+                | TableFlags::PAD_OUTER_X
+                | TableFlags::SORTABLE
+                | TableFlags::SORT_TRISTATE,
             show_icons,
         ) {
+            // update sorting if necessary
+            if let Some(sort_specs) = ui.table_sort_specs_mut() {
+                sort_specs.conditional_sort(|column_specs| {
+                    sorting = Some(column_specs.iter().next().and_then(|column| {
+                        let reverse = column.sort_direction()? == TableSortDirection::Descending;
+                        sortings
+                            .get(column.column_idx())
+                            .copied()
+                            .flatten()
+                            .map(|sorting| (sorting, reverse))
+                    }));
+                });
+            }
+
             let colors = exports::colors();
+            let green = colors.core(CoreColor::LightGreen).unwrap_or(GREEN);
+            let grey = colors.core(CoreColor::MediumGrey).unwrap_or(GREY);
 
-            for (i, build) in self.entries.iter_mut().enumerate() {
-                // check if filters match
-                let prof_matches =
-                    !self.filter_prof || current_prof.map_or(true, |prof| prof == build.prof);
+            for &ViewEntry { id, build, library } in entries {
+                let active = detect.map_or(false, |current| {
+                    build.is_active(defs, current.prof, current.spec, current_food, current_util)
+                });
 
-                if build.visible && prof_matches {
-                    ui.table_next_row();
+                ui.table_next_row();
 
-                    let red = colors.core(CoreColor::LightRed).unwrap_or(RED);
-                    let green = colors.core(CoreColor::LightGreen).unwrap_or(GREEN);
+                // highlight active build
+                if active {
+                    ui.table_set_bg_color(TableBgTarget::ROW_BG0, render::with_alpha(green, 0.15));
+                }
 
-                    // name
-                    ui.table_next_column();
-                    match colors.prof_base(build.prof) {
-                        Some(color) => ui.text_colored(render::with_alpha(color, 1.0), &build.name),
-                        None => ui.text(&build.name),
+                // selection for sharing
+                ui.table_next_column();
+                let reference = Self::reference(build, library);
+                let mut is_selected = selected.contains(&reference);
+                if ui.checkbox(format!("##select-{id}"), &mut is_selected) {
+                    *action = ViewAction::Select(reference, is_selected);
+                }
+                if ui.is_item_hovered() {
+                    ui.tooltip_text("Select for sharing");
+                }
+
+                // name
+                ui.table_next_column();
+                match colors.prof_base(build.prof) {
+                    Some(color) => ui.text_colored(render::with_alpha(color, 1.0), &build.name),
+                    None => ui.text(&build.name),
+                }
+                render::item_context_menu(format!("build-context-{id}"), || {
+                    let title = "Build options";
+                    match colors.core(CoreColor::MediumGrey) {
+                        Some(color) => ui.text_colored(color, title),
+                        None => ui.text(title),
+                    }
+                    if ui.small_button("Copy share code") {
+                        ui.set_clipboard_text(share::encode([build]));
+                    }
+                    for (i, link) in build.links.iter().enumerate() {
+                        if ui.small_button(format!("Open {}##link-{i}", link.display_name())) {
+                            buff_ui::open_url(&link.url);
+                        }
+                    }
+                    if !build.template.is_empty() && ui.small_button("Copy build template") {
+                        ui.set_clipboard_text(&build.template);
                     }
+                    if library && ui.small_button("Copy to personal builds") {
+                        *action = ViewAction::Copy(build.clone());
+                    }
+                });
+
+                // specialization, notes & tags as tooltip
+                let show_notes = !display_notes && !build.notes.is_empty();
+                let show_tags = !build.tags.is_empty();
+                if (build.spec.is_some() || show_notes || show_tags) && ui.is_item_hovered() {
+                    ui.tooltip(|| {
+                        if let Some(spec) = build.spec {
+                            ui.text(spec_name(build.prof, spec));
+                        }
+                        if show_notes {
+                            ui.text(&build.notes);
+                        }
+                        if show_tags {
+                            ui.text_colored(grey, build.tags.join(", "));
+                        }
+                    });
+                }
 
-                    // notes as column or tooltip
-                    if self.display_notes {
-                        ui.table_next_column();
-                        ui.text(&build.notes);
-                    } else if !build.notes.is_empty() && ui.is_item_hovered() {
-                        ui.tooltip_text(&build.notes);
+                // details marker with long notes as tooltip
+                if !build.details.is_empty() {
+                    ui.same_line();
+                    ui.text_colored(grey, "(details)");
+                    if ui.is_item_hovered() {
+                        ui.tooltip(|| {
+                            let _wrap = ui.push_text_wrap_pos_with_pos(DETAILS_WIDTH);
+                            ui.text(&build.details);
+                        });
                     }
+                }
+
+                // library marker
+                if library {
+                    ui.same_line();
+                    ui.text_colored(grey, "(library)");
+                    if ui.is_item_hovered() {
+                        ui.tooltip_text("Read-only build from the build library");
+                    }
+                }
+
+                // assign button for active build
+                if active {
+                    ui.same_line();
+                    if ui.small_button(format!("Assign##assign-{id}")) {
+                        *action = ViewAction::Assign(Self::reference(build, library));
+                    }
+                    if ui.is_item_hovered() {
+                        ui.tooltip_text("Assign this build to the current character");
+                    }
+                }
 
-                    // food
+                // notes as column
+                if display_notes {
                     ui.table_next_column();
-                    if let Some(DefinitionKind::Food(food)) = defs.definition(build.food) {
-                        match current_food {
-                            BuffState::Unknown => ui.text(&food.display),
-                            BuffState::Some(id) if id == food.id => {
-                                ui.text_colored(green, &food.display)
-                            }
-                            _ => ui.text_colored(red, &food.display),
+                    ui.text(&build.notes);
+                }
+
+                // food
+                ui.table_next_column();
+                Self::render_buffs_cell(ui, defs, &build.food, current_food, &colors, |food| {
+                    buff_ui::render_food_context_menu(ui, id, food.id, Some(&food.name), &colors)
+                });
+
+                // util
+                ui.table_next_column();
+                Self::render_buffs_cell(ui, defs, &build.util, current_util, &colors, |util| {
+                    buff_ui::render_util_context_menu(ui, id, util.id, Some(&util.name), &colors)
+                });
+
+                // custom buffs
+                ui.table_next_column();
+                Self::render_custom_cell(ui, custom, &build.custom, current, &colors);
+            }
+        }
+
+        sorting
+    }
+
+    /// Checks whether the build matches the search & profession filter.
+    fn is_shown(&self, build: &Build, current: Option<Current>) -> bool {
+        build.visible
+            && (!self.filter_prof
+                || current.map_or(true, |current| {
+                    build.matches_prof(current.prof, current.spec)
+                }))
+    }
+
+    /// Renders share code import & export.
+    fn render_share(&mut self, ui: &Ui, defs: &Definitions, current: Option<Current>) {
+        let colors = exports::colors();
+        let red = colors.core(CoreColor::LightRed).unwrap_or(RED);
+        let grey = colors.core(CoreColor::MediumGrey).unwrap_or(GREY);
+
+        // export selected or shown builds
+        let selected = self
+            .all()
+            .filter(|(build, library)| self.selected.contains(&Self::reference(build, *library)))
+            .map(|(build, _)| build)
+            .collect::<Vec<_>>();
+        if selected.is_empty() {
+            if ui.button("Share###share") {
+                let shown = self
+                    .all()
+                    .map(|(build, _)| build)
+                    .filter(|build| self.is_shown(build, current))
+                    .collect::<Vec<_>>();
+                if !shown.is_empty() {
+                    ui.set_clipboard_text(share::encode(shown));
+                }
+            }
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Copy share code for all shown builds");
+            }
+        } else {
+            if ui.button(format!("Share {} selected###share", selected.len())) {
+                ui.set_clipboard_text(share::encode(selected));
+            }
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Copy share code for the selected builds");
+            }
+
+            ui.same_line();
+            if ui.button("Clear selection") {
+                self.selected.clear();
+            }
+        }
+
+        // import from clipboard
+        ui.same_line();
+        if ui.button("Import") {
+            self.pending_import = Some(
+                ui.clipboard_text()
+                    .ok_or(ShareError::InvalidPrefix)
+                    .and_then(|code| share::decode(&code)),
+            );
+        }
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Import builds from share code in clipboard");
+        }
+
+        // export shown builds as csv
+        ui.same_line();
+        if ui.button("Export CSV") {
+            let shown = self
+                .all()
+                .map(|(build, _)| build)
+                .filter(|build| self.is_shown(build, current));
+            ui.set_clipboard_text(spreadsheet::export(defs, shown));
+        }
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Copy all shown builds as CSV");
+        }
+
+        // import csv from clipboard
+        ui.same_line();
+        if ui.button("Import CSV") {
+            let imported = spreadsheet::import(defs, &ui.clipboard_text().unwrap_or_default());
+            self.import_errors = imported.errors;
+            self.pending_import = if imported.builds.is_empty() {
+                None
+            } else {
+                Some(Ok(imported.builds))
+            };
+        }
+        if ui.is_item_hovered() {
+            ui.tooltip_text(
+                "Import builds from CSV in clipboard\nColumns: profession, name, notes, food, util",
+            );
+        }
+
+        // unresolved spreadsheet rows
+        if !self.import_errors.is_empty() {
+            ui.text_colored(
+                red,
+                format!("{} rows could not be imported", self.import_errors.len()),
+            );
+            if ui.is_item_hovered() {
+                ui.tooltip(|| {
+                    for (line, err) in &self.import_errors {
+                        ui.text(format!("Line {line}: {}", err.description()));
+                    }
+                });
+            }
+            ui.same_line();
+            if ui.small_button("Dismiss##import-errors") {
+                self.import_errors.clear();
+            }
+        }
+
+        // pending import
+        match &self.pending_import {
+            None => {}
+            Some(Err(err)) => {
+                let message = match err {
+                    ShareError::InvalidPrefix => "Clipboard contains no build share code".into(),
+                    ShareError::UnsupportedVersion(version) => {
+                        format!("Share code version {version} is not supported")
+                    }
+                    ShareError::InvalidEncoding => "Share code is malformed".into(),
+                    ShareError::InvalidJSON => "Share code contains invalid builds".into(),
+                    ShareError::Empty => "Share code contains no builds".into(),
+                };
+                ui.text_colored(red, message);
+                ui.same_line();
+                if ui.small_button("Dismiss") {
+                    self.pending_import = None;
+                }
+            }
+            Some(Ok(builds)) => {
+                let conflicts = self.conflicts(builds);
+                ui.text(format!("Import {} builds", builds.len()));
+                if ui.is_item_hovered() {
+                    ui.tooltip(|| {
+                        for build in builds {
+                            ui.text(&build.name);
                         }
+                    });
+                }
+                if conflicts > 0 {
+                    ui.same_line();
+                    ui.text_colored(grey, format!("({conflicts} with existing names)"));
+                }
 
-                        buff_ui::render_buff_tooltip(ui, food);
-                        buff_ui::render_food_context_menu(
-                            ui,
-                            i,
-                            food.id,
-                            Some(&food.name),
-                            &colors,
-                        );
+                let mut conflict = None;
+                if conflicts > 0 {
+                    if ui.small_button("Replace") {
+                        conflict = Some(Conflict::Replace);
+                    }
+                    ui.same_line();
+                    if ui.small_button("Keep both") {
+                        conflict = Some(Conflict::KeepBoth);
                     }
+                    ui.same_line();
+                    if ui.small_button("Skip existing") {
+                        conflict = Some(Conflict::Skip);
+                    }
+                } else if ui.small_button("Import##confirm") {
+                    conflict = Some(Conflict::Skip);
+                }
+                ui.same_line();
+                let cancel = ui.small_button("Cancel");
 
-                    // util
-                    ui.table_next_column();
-                    if let Some(DefinitionKind::Util(util)) = defs.definition(build.util) {
-                        match current_util {
-                            BuffState::Unknown => ui.text(&util.display),
-                            BuffState::Some(id) if id == util.id => {
-                                ui.text_colored(green, &util.display)
+                if let Some(conflict) = conflict {
+                    if let Some(Ok(builds)) = self.pending_import.take() {
+                        self.import(builds, conflict);
+                    }
+                } else if cancel {
+                    self.pending_import = None;
+                }
+            }
+        }
+    }
+
+    /// Renders a cell with the primary buff and the amount of acceptable alternatives.
+    fn render_buffs_cell(
+        ui: &Ui,
+        defs: &Definitions,
+        ids: &[u32],
+        current: BuffState<u32>,
+        colors: &exports::Colors,
+        context_menu: impl FnOnce(&BuffData),
+    ) {
+        let red = colors.core(CoreColor::LightRed).unwrap_or(RED);
+        let green = colors.core(CoreColor::LightGreen).unwrap_or(GREEN);
+        let grey = colors.core(CoreColor::MediumGrey).unwrap_or(GREY);
+
+        let mut buffs = ids
+            .iter()
+            .filter_map(|id| defs.definition(*id).and_then(DefinitionKind::data));
+
+        if let Some(primary) = buffs.next() {
+            match current {
+                BuffState::Unknown => ui.text(&primary.display),
+                BuffState::Some(id) if ids.contains(&id) => {
+                    ui.text_colored(green, &primary.display)
+                }
+                _ => ui.text_colored(red, &primary.display),
+            }
+            buff_ui::render_buff_tooltip(ui, primary);
+            context_menu(primary);
+
+            // alternatives as tooltip
+            let alternatives = buffs.collect::<Vec<_>>();
+            if !alternatives.is_empty() {
+                ui.same_line();
+                ui.text_colored(grey, format!("+{}", alternatives.len()));
+                if ui.is_item_hovered() {
+                    ui.tooltip(|| {
+                        ui.text_colored(grey, "Alternatives");
+                        for buff in alternatives {
+                            match current {
+                                BuffState::Some(id) if id == buff.id => {
+                                    ui.text_colored(green, &buff.name)
+                                }
+                                _ => ui.text(&buff.name),
                             }
-                            _ => ui.text_colored(red, &util.display),
                         }
+                    });
+                }
+            }
+        }
+    }
+
+    /// Renders a cell with the required custom buffs.
+    fn render_custom_cell(
+        ui: &Ui,
+        all: &[CustomReminder],
+        ids: &[u32],
+        current: Option<Current>,
+        colors: &exports::Colors,
+    ) {
+        let red = colors.core(CoreColor::LightRed).unwrap_or(RED);
+        let green = colors.core(CoreColor::LightGreen).unwrap_or(GREEN);
+
+        let required = all
+            .iter()
+            .filter(|remind| ids.contains(&remind.id))
+            .collect::<Vec<_>>();
+        let state = |remind: &CustomReminder| {
+            current.map_or(BuffState::Unknown, |current| {
+                current.buffs.custom_state(remind.id)
+            })
+        };
 
-                        buff_ui::render_buff_tooltip(ui, util);
-                        buff_ui::render_util_context_menu(
-                            ui,
-                            i,
-                            util.id,
-                            Some(&util.name),
-                            &colors,
-                        );
+        ui.group(|| {
+            for remind in &required {
+                let short = remind.short();
+                match state(remind) {
+                    BuffState::Unknown => ui.text(short),
+                    BuffState::None => ui.text_colored(red, short),
+                    BuffState::Some(_) => ui.text_colored(green, short),
+                }
+                ui.same_line_with_spacing(0.0, 0.0);
+            }
+        });
+        if !required.is_empty() && ui.is_item_hovered() {
+            ui.tooltip(|| {
+                for remind in &required {
+                    let name = remind.display_name();
+                    match state(remind) {
+                        BuffState::Unknown => ui.text(name),
+                        BuffState::None => ui.text_colored(red, name),
+                        BuffState::Some(_) => ui.text_colored(green, name),
                     }
                 }
+            });
+        }
+    }
+
+    /// Renders a combo for selecting required custom buffs.
+    fn render_custom_input(ui: &Ui, id: &str, all: &[CustomReminder], ids: &mut Vec<u32>) {
+        let preview = match ids.as_slice() {
+            [] => "None".to_string(),
+            [single] => all
+                .iter()
+                .find(|remind| remind.id == *single)
+                .map(|remind| remind.display_name().to_string())
+                .unwrap_or_else(|| single.to_string()),
+            multiple => format!("{} buffs", multiple.len()),
+        };
+
+        // toggle required buffs
+        if let Some(remind) = Combo::new(format!("##{id}"), |remind: &&CustomReminder| {
+            remind.display_name().into()
+        })
+        .keep_open(true)
+        .build(ui, preview, all, |remind| ids.contains(&remind.id))
+        {
+            if ids.contains(&remind.id) {
+                ids.retain(|required_id| *required_id != remind.id);
+            } else {
+                ids.push(remind.id);
             }
         }
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Custom buffs required by the build");
+        }
+    }
+
+    /// Renders inputs for a list of buffs.
+    fn render_buffs_input<'b>(
+        ui: &Ui,
+        id: &str,
+        ids: &mut Vec<u32>,
+        all: impl Iterator<Item = &'b BuffData> + Clone,
+        placeholder: u32,
+    ) {
+        const INPUT_SIZE: f32 = 100.0;
+
+        let mut remove = None;
+        for (i, buff_id) in ids.iter_mut().enumerate() {
+            ui.set_next_item_width(INPUT_SIZE);
+            if let Some(changed) =
+                buff_ui::render_buff_combo(ui, format!("##{id}-{i}"), *buff_id, all.clone())
+            {
+                *buff_id = changed.id;
+            }
+            if i > 0 {
+                ui.same_line();
+                if ui.small_button(format!("x##{id}-remove-{i}")) {
+                    remove = Some(i);
+                }
+                if ui.is_item_hovered() {
+                    ui.tooltip_text("Remove alternative");
+                }
+            }
+        }
+        if let Some(i) = remove {
+            ids.remove(i);
+        }
+
+        if ui.small_button(format!("+##{id}-add")) {
+            ids.push(placeholder);
+        }
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Add alternative");
+        }
+    }
+
+    /// Renders a button opening a popup to edit long-form notes & links.
+    fn render_details_input(ui: &Ui, id: &str, build: &mut Build) {
+        let popup = format!("##{id}-popup");
+        let label = if build.details.is_empty() && build.links.is_empty() {
+            "Add"
+        } else {
+            "Edit"
+        };
+        if ui.small_button(format!("{label}##{id}")) {
+            ui.open_popup(&popup);
+        }
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Edit long notes & links");
+        }
+
+        ui.popup(&popup, || {
+            ui.text("Details");
+            ui.input_text_multiline(
+                format!("##{id}-details"),
+                &mut build.details,
+                [DETAILS_WIDTH, 0.0],
+            )
+            .build();
+
+            ui.text("Links");
+            let mut remove = None;
+            for (i, link) in build.links.iter_mut().enumerate() {
+                ui.set_next_item_width(0.25 * DETAILS_WIDTH);
+                ui.input_text(format!("##{id}-link-name-{i}"), &mut link.name)
+                    .hint("Name")
+                    .build();
+                ui.same_line();
+                ui.set_next_item_width(0.75 * DETAILS_WIDTH - 30.0);
+                ui.input_text(format!("##{id}-link-url-{i}"), &mut link.url)
+                    .hint("https://")
+                    .build();
+                ui.same_line();
+                if ui.small_button(format!("x##{id}-link-remove-{i}")) {
+                    remove = Some(i);
+                }
+            }
+            if let Some(index) = remove {
+                build.links.remove(index);
+            }
+            if ui.small_button(format!("Add link##{id}-link-add")) {
+                build.links.push(BuildLink::empty());
+            }
+        });
     }
 
     /// Renders edit mode contents.
-    fn render_edit(&mut self, ui: &Ui, defs: &Definitions, show_icons: bool) {
+    fn render_edit(
+        &mut self,
+        ui: &Ui,
+        defs: &Definitions,
+        custom: &[CustomReminder],
+        show_icons: bool,
+    ) {
+        let colors = exports::colors();
+        let red = colors.core(CoreColor::LightRed).unwrap_or(RED);
+
         // render builds table
         if let Some(_table) = render::table_with_icons(
             ui,
             "##builds-table",
             &[
                 TableIconColumn::new("Profession", None),
+                TableIconColumn::new("Spec", None),
                 TableIconColumn::new("Name", None),
                 TableIconColumn::new("Notes", None),
+                TableIconColumn::new("Tags", None),
+                TableIconColumn::new("Template", None),
+                TableIconColumn::new("Details", None),
                 TableIconColumn::new("Food", FOOD_ICON.as_ref()),
                 TableIconColumn::new("Utility", UTIL_ICON.as_ref()),
+                TableIconColumn::new("Buffs", UNKNOWN_ICON.as_ref()),
                 TableIconColumn::new("##actions", None),
             ],
             TableFlags::SIZING_STRETCH_PROP | TableFlags::PAD_OUTER_X,
@@ -163,7 +881,25 @@ impl Builds {
                 // prof select
                 ui.table_next_column();
                 ui.set_next_item_width(INPUT_SIZE);
-                render_prof_select(ui, format!("##prof-{i}"), &mut build.prof);
+                if render_prof_select(ui, format!("##prof-{i}"), &mut build.prof) {
+                    build.spec = None;
+                }
+
+                // spec select
+                ui.table_next_column();
+                ui.set_next_item_width(INPUT_SIZE);
+                let prof = build.prof;
+                render_combo(
+                    ui,
+                    format!("##spec-{i}"),
+                    iter::once(None).chain(elite_specs(prof).map(Some)),
+                    &mut build.spec,
+                    |spec| match spec {
+                        Some(spec) => spec_name(prof, *spec).into(),
+                        None => "Any".into(),
+                    },
+                    |_| None,
+                );
 
                 // name input
                 ui.table_next_column();
@@ -177,57 +913,113 @@ impl Builds {
                 ui.input_text(format!("##notes-{i}"), &mut build.notes)
                     .build();
 
-                // food select
+                // tags input
                 ui.table_next_column();
                 ui.set_next_item_width(INPUT_SIZE);
-                if let Some(changed) = buff_ui::render_buff_combo(
+                let mut tags = build.tags.join(", ");
+                if ui
+                    .input_text(format!("##tags-{i}"), &mut tags)
+                    .hint("raid, fractal")
+                    .build()
+                {
+                    build.tags = tags
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|tag| !tag.is_empty())
+                        .map(Into::into)
+                        .collect();
+                }
+                if ui.is_item_hovered() {
+                    ui.tooltip_text("Comma separated tags");
+                }
+
+                // template input
+                ui.table_next_column();
+                ui.set_next_item_width(INPUT_SIZE);
+                let result = template::decode(&build.template);
+                let style = (!build.template.is_empty() && result.is_err())
+                    .then(|| ui.push_style_color(StyleColor::Text, red));
+                if ui
+                    .input_text(format!("##template-{i}"), &mut build.template)
+                    .hint("[&DQ...]")
+                    .build()
+                {
+                    // fill profession & elite specialization from template
+                    if let Ok(Template { prof, spec }) = template::decode(&build.template) {
+                        build.prof = prof;
+                        build.spec = spec;
+                    }
+                }
+                drop(style);
+                if ui.is_item_hovered() {
+                    ui.tooltip_text(match result {
+                        _ if build.template.is_empty() => "Paste a build template chat link",
+                        Ok(_) => "Build template chat link",
+                        Err(TemplateError::InvalidLink) => "Not a chat link",
+                        Err(TemplateError::InvalidEncoding) => "Chat link is malformed",
+                        Err(TemplateError::NotTemplate) => "Chat link is not a build template",
+                        Err(TemplateError::TooShort) => "Build template is incomplete",
+                        Err(TemplateError::UnknownProfession(_)) => {
+                            "Build template has an unknown profession"
+                        }
+                    });
+                }
+
+                // details & links input
+                ui.table_next_column();
+                Self::render_details_input(ui, &format!("details-{i}"), build);
+
+                // food select
+                ui.table_next_column();
+                Self::render_buffs_input(
                     ui,
-                    format!("##food-{i}"),
-                    build.food,
+                    &format!("food-{i}"),
+                    &mut build.food,
                     defs.all_food(),
-                ) {
-                    build.food = changed.id;
-                }
+                    MALNOURISHED,
+                );
 
                 // util select
                 ui.table_next_column();
-                ui.set_next_item_width(INPUT_SIZE);
-                if let Some(changed) = buff_ui::render_buff_combo(
+                Self::render_buffs_input(
                     ui,
-                    format!("##util-{i}"),
-                    build.util,
+                    &format!("util-{i}"),
+                    &mut build.util,
                     defs.all_util(),
-                ) {
-                    build.util = changed.id;
-                }
+                    DIMINISHED,
+                );
+
+                // custom select
+                ui.table_next_column();
+                ui.set_next_item_width(INPUT_SIZE);
+                Self::render_custom_input(ui, &format!("custom-{i}"), custom, &mut build.custom);
 
                 // buttons
                 ui.table_next_column();
                 action.render_buttons(ui, "actions", i, len);
             }
             action.perform(&mut self.entries);
+            if self.entries.len() < len {
+                self.clean_assignments();
+            }
         }
 
         // add button
         if ui.button("Add") {
-            self.entries.push(Build::empty());
+            self.add(Build::empty());
         }
     }
 }
 
 impl<'p> Component<Props<'p>> for Builds {
     /// Renders the builds UI.
-    fn render(
-        &mut self,
-        ui: &Ui,
-        (defs, current_prof, current_food, current_util, show_icons): Props<'p>,
-    ) {
+    fn render(&mut self, ui: &Ui, (defs, custom, current, show_icons): Props<'p>) {
         let _style = render::small_padding(ui);
 
         // profession filter
         ui.checkbox("Current profession", &mut self.filter_prof);
         if ui.is_item_hovered() {
-            ui.tooltip_text("Only show builds for current profession");
+            ui.tooltip_text("Only show builds for current profession & elite specialization");
         }
 
         // edit mode button
@@ -235,29 +1027,37 @@ impl<'p> Component<Props<'p>> for Builds {
         if self.edit {
             if ui.button("Done") {
                 self.edit = false;
-                self.refresh_search();
+                self.search_outdated = true;
             }
         } else if ui.button("Edit") {
             self.edit = true;
         }
 
+        // share buttons
+        ui.same_line_with_spacing(0.0, 10.0);
+        self.render_share(ui, defs, current);
+
+        // grouping select
+        ui.same_line_with_spacing(0.0, 10.0);
+        ui.set_next_item_width(100.0);
+        render_enum_combo(ui, "Group by", &mut self.grouping);
+
         // search field
         if ui.input_text("##search", &mut self.search).build() {
-            self.refresh_search();
+            self.search_outdated = true;
+        }
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Search name, notes, tags, profession, food & utility");
+        }
+        if self.search_outdated {
+            self.refresh_search(defs);
         }
 
         // contents
         if self.edit {
-            self.render_edit(ui, defs, show_icons);
+            self.render_edit(ui, defs, custom, show_icons);
         } else {
-            self.render_view(
-                ui,
-                defs,
-                current_prof,
-                current_food,
-                current_util,
-                show_icons,
-            );
+            self.render_view(ui, defs, custom, current, show_icons);
         }
     }
 }
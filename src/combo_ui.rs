@@ -1,15 +1,314 @@
 use crate::data::PROFESSIONS;
-use arc_util::ui::render;
+use arc_util::{
+    colors::{Color, GREY},
+    ui::render,
+};
 use arcdps::{
-    exports,
-    imgui::{Selectable, StyleColor, Ui},
+    exports::{self, CoreColor},
+    imgui::{ConfigFlags, Key, Selectable, SelectableFlags, StyleColor, Ui},
     Profession,
 };
-use std::borrow::Cow;
+use std::{borrow::Cow, collections::BTreeMap, sync::Mutex};
 use strum::IntoEnumIterator;
 
+/// Minimum amount of items for a combo to show a search input by default.
+const SEARCH_THRESHOLD: usize = 8;
+
+/// State of the currently open combo.
+///
+/// Only a single combo can be open at once, so the state is shared.
+static COMBO_STATE: Mutex<ComboState> = Mutex::new(ComboState::new());
+
+/// State of an open combo.
+#[derive(Debug)]
+struct ComboState {
+    /// Search input contents.
+    search: String,
+
+    /// Selected grouping, `0` for no grouping.
+    ///
+    /// Index into the groupings of the open combo, so it is reset when opened.
+    grouping: usize,
+
+    /// Index of the item selected via keyboard navigation.
+    ///
+    /// Follows the focused item if ImGui keyboard navigation is enabled.
+    nav: Option<usize>,
+}
+
+impl ComboState {
+    const fn new() -> Self {
+        Self {
+            search: String::new(),
+            grouping: 0,
+            nav: None,
+        }
+    }
+}
+
+/// Group of a combo item with its sort order & name.
+///
+/// Groups are sorted by order first and by name second.
+pub type ComboGroup = (i32, String);
+
+/// Function returning a label for an item.
+type LabelFn<'a, T> = Box<dyn Fn(&T) -> Cow<str> + 'a>;
+
+/// Function returning a group for an item.
+type GroupFn<'a, T> = Box<dyn Fn(&T) -> Option<ComboGroup> + 'a>;
+
+/// Combo box with search, per-item color & tooltip, grouping and keyboard navigation.
+pub struct Combo<'a, T> {
+    label: String,
+    item_label: LabelFn<'a, T>,
+    item_color: Option<Box<dyn Fn(&T) -> Option<Color> + 'a>>,
+    item_tooltip: Option<Box<dyn Fn(&Ui, &T) + 'a>>,
+    item_search: Option<Box<dyn Fn(&T, &str) -> bool + 'a>>,
+    groupings: Vec<(&'a str, GroupFn<'a, T>)>,
+    searchable: Option<bool>,
+    keep_open: bool,
+}
+
+impl<'a, T> Combo<'a, T> {
+    /// Creates a new combo with the given label & item labels.
+    pub fn new(label: impl Into<String>, item_label: impl Fn(&T) -> Cow<str> + 'a) -> Self {
+        Self {
+            label: label.into(),
+            item_label: Box::new(item_label),
+            item_color: None,
+            item_tooltip: None,
+            item_search: None,
+            groupings: Vec::new(),
+            searchable: None,
+            keep_open: false,
+        }
+    }
+
+    /// Sets the text color for items.
+    pub fn color(mut self, item_color: impl Fn(&T) -> Option<Color> + 'a) -> Self {
+        self.item_color = Some(Box::new(item_color));
+        self
+    }
+
+    /// Sets the tooltip contents for hovered items.
+    pub fn tooltip(mut self, item_tooltip: impl Fn(&Ui, &T) + 'a) -> Self {
+        self.item_tooltip = Some(Box::new(item_tooltip));
+        self
+    }
+
+    /// Sets an additional search matching items by more than their label.
+    ///
+    /// The search text passed is lowercase.
+    pub fn search(mut self, item_search: impl Fn(&T, &str) -> bool + 'a) -> Self {
+        self.item_search = Some(Box::new(item_search));
+        self.searchable = Some(true);
+        self
+    }
+
+    /// Sets whether to show the search input.
+    ///
+    /// By default the search input is shown for combos with many items.
+    pub fn searchable(mut self, searchable: bool) -> Self {
+        self.searchable = Some(searchable);
+        self
+    }
+
+    /// Sets whether to keep the combo open after selecting an item.
+    ///
+    /// Useful for toggling multiple items.
+    pub fn keep_open(mut self, keep_open: bool) -> Self {
+        self.keep_open = keep_open;
+        self
+    }
+
+    /// Adds a grouping the user can choose.
+    ///
+    /// Items without a group are listed first.
+    pub fn grouping(
+        mut self,
+        name: &'a str,
+        item_group: impl Fn(&T) -> Option<ComboGroup> + 'a,
+    ) -> Self {
+        self.groupings.push((name, Box::new(item_group)));
+        self
+    }
+
+    /// Checks whether the item matches the lowercase search.
+    fn matches(&self, item: &T, search: &str) -> bool {
+        search.is_empty()
+            || (self.item_label)(item).to_lowercase().contains(search)
+            || self
+                .item_search
+                .as_ref()
+                .map_or(false, |item_search| item_search(item, search))
+    }
+
+    /// Renders the combo.
+    ///
+    /// Returns the newly selected item.
+    pub fn build(
+        self,
+        ui: &Ui,
+        preview: impl AsRef<str>,
+        all: impl IntoIterator<Item = T>,
+        is_selected: impl Fn(&T) -> bool,
+    ) -> Option<T> {
+        let _token = ui.begin_combo(&self.label, preview)?;
+        let mut state = COMBO_STATE.lock().unwrap();
+
+        // reset state when opened
+        let appearing = ui.is_window_appearing();
+        if appearing {
+            state.search.clear();
+            state.grouping = 0;
+            state.nav = None;
+        }
+
+        let all = all.into_iter().collect::<Vec<_>>();
+        let searchable = self.searchable.unwrap_or(all.len() >= SEARCH_THRESHOLD);
+
+        // search input
+        let mut submit = false;
+        if searchable {
+            if appearing {
+                ui.set_keyboard_focus_here();
+            }
+            let previous = state.search.len();
+            submit = ui
+                .input_text("##combo-search", &mut state.search)
+                .hint("Search")
+                .enter_returns_true(true)
+                .build();
+            if state.search.len() != previous {
+                state.nav = None;
+            }
+        }
+
+        // grouping select
+        if !self.groupings.is_empty() {
+            ui.text("Group:");
+            ui.same_line();
+            ui.radio_button("None", &mut state.grouping, 0);
+            for (i, (name, _)) in self.groupings.iter().enumerate() {
+                ui.same_line();
+                ui.radio_button(name, &mut state.grouping, i + 1);
+            }
+        }
+        if searchable || !self.groupings.is_empty() {
+            ui.separator();
+        }
+
+        // filter & group items
+        let search = state.search.to_lowercase();
+        let grouping = state
+            .grouping
+            .checked_sub(1)
+            .and_then(|i| self.groupings.get(i));
+        let mut groups = BTreeMap::<_, Vec<_>>::new();
+        for item in all {
+            if self.matches(&item, &search) {
+                let group = grouping.and_then(|(_, item_group)| item_group(&item));
+                groups.entry(group).or_default().push(item);
+            }
+        }
+
+        // keyboard navigation, imgui navigation moves focus itself if enabled
+        let imgui_nav = ui
+            .io()
+            .config_flags
+            .contains(ConfigFlags::NAV_ENABLE_KEYBOARD);
+        let len = groups.values().map(Vec::len).sum::<usize>();
+        let mut nav_changed = false;
+        if searchable && len > 0 && !imgui_nav {
+            if ui.is_key_pressed(Key::DownArrow) {
+                state.nav = Some(state.nav.map_or(0, |nav| (nav + 1).min(len - 1)));
+                nav_changed = true;
+            }
+            if ui.is_key_pressed(Key::UpArrow) {
+                state.nav = Some(state.nav.map_or(0, |nav| nav.saturating_sub(1)));
+                nav_changed = true;
+            }
+        }
+        let nav = state.nav;
+        drop(state);
+
+        let colors = exports::colors();
+        let grey = colors.core(CoreColor::MediumGrey).unwrap_or(GREY);
+
+        let mut result = None;
+        let mut focused = None;
+        let mut index = 0;
+        for (group, items) in groups {
+            if let Some((_, name)) = group {
+                ui.text_colored(grey, name);
+            }
+
+            for item in items {
+                let selected = match nav {
+                    Some(nav) => nav == index,
+                    None => is_selected(&item),
+                };
+
+                // apply color to selectable
+                let style = self
+                    .item_color
+                    .as_ref()
+                    .and_then(|item_color| item_color(&item))
+                    .map(|color| ui.push_style_color(StyleColor::Text, color));
+                let flags = if self.keep_open {
+                    SelectableFlags::DONT_CLOSE_POPUPS
+                } else {
+                    SelectableFlags::empty()
+                };
+                let clicked = Selectable::new((self.item_label)(&item))
+                    .flags(flags)
+                    .selected(selected)
+                    .build(ui);
+                drop(style);
+
+                if imgui_nav && ui.is_item_focused() {
+                    focused = Some(index);
+                }
+
+                // handle focus & scroll
+                if selected {
+                    if nav.is_none() {
+                        ui.set_item_default_focus();
+                    } else if nav_changed {
+                        ui.set_scroll_here_y();
+                    }
+                }
+
+                // tooltip
+                if ui.is_item_hovered() {
+                    if let Some(item_tooltip) = &self.item_tooltip {
+                        ui.tooltip(|| item_tooltip(ui, &item));
+                    }
+                }
+
+                // submit navigated or first item on enter
+                let submitted = submit && index == nav.unwrap_or(0);
+                if submitted && !self.keep_open {
+                    ui.close_current_popup();
+                }
+                if clicked || submitted {
+                    result = Some(item);
+                }
+
+                index += 1;
+            }
+        }
+
+        // keep navigation in sync with imgui focus
+        if imgui_nav {
+            COMBO_STATE.lock().unwrap().nav = focused;
+        }
+
+        result
+    }
+}
+
 /// Renders a combo box for items from an iterator.
-// TODO: make generic enough to be used for buff combo & co as well?
 pub fn render_combo<T>(
     ui: &Ui,
     label: impl AsRef<str>,
@@ -21,30 +320,18 @@ pub fn render_combo<T>(
 where
     T: PartialEq,
 {
-    let mut changed = false;
-    if let Some(_token) = ui.begin_combo(label, item_label(current)) {
-        for entry in all {
-            let selected = entry == *current;
-
-            // apply color to selectable
-            let style =
-                item_color(&entry).map(|color| ui.push_style_color(StyleColor::Text, color));
-            if Selectable::new(item_label(&entry))
-                .selected(selected)
-                .build(ui)
-            {
-                changed = true;
-                *current = entry;
-            }
-            drop(style);
+    let preview = item_label(current).into_owned();
+    let selected = Combo::new(label.as_ref(), item_label)
+        .color(item_color)
+        .build(ui, preview, all, |item| *item == *current);
 
-            // handle focus
-            if selected {
-                ui.set_item_default_focus();
-            }
+    match selected {
+        Some(item) => {
+            *current = item;
+            true
         }
+        None => false,
     }
-    changed
 }
 
 /// Renders a combo box for an enum implementing [`IntoEnumIterator`].
@@ -1,4 +1,4 @@
-use arcdps::Profession;
+use arcdps::{Profession, Specialization};
 
 /// Text shown when buff state is not known (yet).
 pub const UNKNOWN_STATE_TEXT: &str = "?";
@@ -32,6 +32,128 @@ pub const PROFESSIONS: &[Profession] = &[
     Profession::Warrior,
 ];
 
+/// Short tags for professions.
+pub const PROFESSION_TAGS: &[(Profession, &str)] = &[
+    (Profession::Elementalist, "Ele"),
+    (Profession::Engineer, "Eng"),
+    (Profession::Guardian, "Gdn"),
+    (Profession::Mesmer, "Mes"),
+    (Profession::Necromancer, "Nec"),
+    (Profession::Ranger, "Rgr"),
+    (Profession::Revenant, "Rev"),
+    (Profession::Thief, "Thf"),
+    (Profession::Warrior, "War"),
+];
+
+/// Elite specializations with their profession & short tag.
+///
+/// Sorted by profession & release.
+pub const ELITE_SPECS: &[(Specialization, Profession, &str)] = &[
+    (Specialization::Tempest, Profession::Elementalist, "Tmp"),
+    (Specialization::Weaver, Profession::Elementalist, "Wea"),
+    (Specialization::Catalyst, Profession::Elementalist, "Cat"),
+    (Specialization::Scrapper, Profession::Engineer, "Scr"),
+    (Specialization::Holosmith, Profession::Engineer, "Holo"),
+    (Specialization::Mechanist, Profession::Engineer, "Mech"),
+    (Specialization::Dragonhunter, Profession::Guardian, "DH"),
+    (Specialization::Firebrand, Profession::Guardian, "FB"),
+    (Specialization::Willbender, Profession::Guardian, "WB"),
+    (Specialization::Chronomancer, Profession::Mesmer, "Chr"),
+    (Specialization::Mirage, Profession::Mesmer, "Mir"),
+    (Specialization::Virtuoso, Profession::Mesmer, "Virt"),
+    (Specialization::Reaper, Profession::Necromancer, "Rea"),
+    (Specialization::Scourge, Profession::Necromancer, "Scg"),
+    (Specialization::Harbinger, Profession::Necromancer, "Harb"),
+    (Specialization::Druid, Profession::Ranger, "Dru"),
+    (Specialization::Soulbeast, Profession::Ranger, "Slb"),
+    (Specialization::Untamed, Profession::Ranger, "Unt"),
+    (Specialization::Herald, Profession::Revenant, "Her"),
+    (Specialization::Renegade, Profession::Revenant, "Ren"),
+    (Specialization::Vindicator, Profession::Revenant, "Vin"),
+    (Specialization::Daredevil, Profession::Thief, "DD"),
+    (Specialization::Deadeye, Profession::Thief, "DE"),
+    (Specialization::Specter, Profession::Thief, "Spec"),
+    (Specialization::Berserker, Profession::Warrior, "Brs"),
+    (Specialization::Spellbreaker, Profession::Warrior, "SB"),
+    (Specialization::Bladesworn, Profession::Warrior, "BS"),
+];
+
+/// Returns all elite specializations of the given profession.
+pub fn elite_specs(prof: Profession) -> impl Iterator<Item = Specialization> + Clone {
+    ELITE_SPECS
+        .iter()
+        .filter(move |(_, spec_prof, _)| *spec_prof == prof)
+        .map(|(spec, _, _)| *spec)
+}
+
+/// Returns the short tag for the elite specialization or the profession as fallback.
+pub fn spec_tag(prof: Profession, spec: Specialization) -> &'static str {
+    ELITE_SPECS
+        .iter()
+        .find(|(elite, _, _)| *elite == spec)
+        .map(|(_, _, tag)| *tag)
+        .or_else(|| {
+            PROFESSION_TAGS
+                .iter()
+                .find(|(entry, _)| *entry == prof)
+                .map(|(_, tag)| *tag)
+        })
+        .unwrap_or("?")
+}
+
+/// Returns the name for the elite specialization or the profession as fallback.
+pub fn spec_name(prof: Profession, spec: Specialization) -> &'static str {
+    if ELITE_SPECS.iter().any(|(elite, _, _)| *elite == spec) {
+        spec.into()
+    } else {
+        prof.into()
+    }
+}
+
+/// Profession codes used in build template chat links.
+pub const PROFESSION_CODES: &[(u8, Profession)] = &[
+    (1, Profession::Guardian),
+    (2, Profession::Warrior),
+    (3, Profession::Engineer),
+    (4, Profession::Ranger),
+    (5, Profession::Thief),
+    (6, Profession::Elementalist),
+    (7, Profession::Mesmer),
+    (8, Profession::Necromancer),
+    (9, Profession::Revenant),
+];
+
+/// Elite specialization ids used in build template chat links.
+pub const ELITE_SPEC_IDS: &[(u8, Specialization)] = &[
+    (5, Specialization::Druid),
+    (7, Specialization::Daredevil),
+    (18, Specialization::Berserker),
+    (27, Specialization::Dragonhunter),
+    (34, Specialization::Reaper),
+    (40, Specialization::Chronomancer),
+    (43, Specialization::Scrapper),
+    (48, Specialization::Tempest),
+    (52, Specialization::Herald),
+    (55, Specialization::Soulbeast),
+    (56, Specialization::Weaver),
+    (57, Specialization::Holosmith),
+    (58, Specialization::Deadeye),
+    (59, Specialization::Mirage),
+    (60, Specialization::Scourge),
+    (61, Specialization::Spellbreaker),
+    (62, Specialization::Firebrand),
+    (63, Specialization::Renegade),
+    (64, Specialization::Harbinger),
+    (65, Specialization::Willbender),
+    (66, Specialization::Virtuoso),
+    (67, Specialization::Catalyst),
+    (68, Specialization::Bladesworn),
+    (69, Specialization::Vindicator),
+    (70, Specialization::Mechanist),
+    (71, Specialization::Specter),
+    (72, Specialization::Untamed),
+];
+
 /// Ids of all raid maps.
 pub const RAID_MAPS: &[u32] = &[
     1155, // aerodrome
@@ -69,3 +191,79 @@ pub const FRACTAL_MAPS: &[u32] = &[
     1309, // siren's reef
     1384, // sunqua peak
 ];
+
+/// Ids of all strike mission maps.
+pub const STRIKE_MAPS: &[u32] = &[
+    1332, // shiverpeaks pass
+    1341, // fraenir of jormag
+    1339, // boneskinner
+    1346, // voice & claw of the fallen
+    1359, // whisper of jormag
+    1368, // forging steel
+    1374, // cold war
+    1432, // aetherblade hideout
+    1450, // xunlai jade junkyard
+    1451, // kaineng overlook
+    1437, // harvest temple
+    1485, // old lion's court
+];
+
+/// Species ids & names of known bosses.
+pub const BOSSES: &[(u32, &str)] = &[
+    // raids
+    (15438, "Vale Guardian"),
+    (15429, "Gorseval"),
+    (15375, "Sabetha"),
+    (16123, "Slothasor"),
+    (16088, "Bandit Trio"),
+    (16115, "Matthias"),
+    (16253, "Escort"),
+    (16235, "Keep Construct"),
+    (16247, "Twisted Castle"),
+    (16246, "Xera"),
+    (17194, "Cairn"),
+    (17172, "Mursaat Overseer"),
+    (17188, "Samarog"),
+    (17154, "Deimos"),
+    (19767, "Soulless Horror"),
+    (19828, "River of Souls"),
+    (19691, "Broken King"),
+    (19536, "Eater of Souls"),
+    (19651, "Eyes"),
+    (19450, "Dhuum"),
+    (43974, "Conjured Amalgamate"),
+    (21105, "Twin Largos"),
+    (20934, "Qadim"),
+    (22006, "Cardinal Adina"),
+    (21964, "Cardinal Sabir"),
+    (22000, "Qadim the Peerless"),
+    // fractals
+    (17021, "MAMA"),
+    (17028, "Siax"),
+    (16948, "Ensolyss"),
+    (17632, "Skorvald"),
+    (17949, "Artsariiv"),
+    (17759, "Arkk"),
+    (23254, "Ai"),
+    (25577, "Kanaxai"),
+    // strikes
+    (22154, "Icebrood Construct"),
+    (22343, "Voice & Claw of the Fallen"),
+    (22492, "Fraenir of Jormag"),
+    (22521, "Boneskinner"),
+    (22711, "Whisper of Jormag"),
+    (22836, "Cold War"),
+    (24033, "Aetherblade Hideout"),
+    (23957, "Xunlai Jade Junkyard"),
+    (24485, "Kaineng Overlook"),
+    (24375, "Harvest Temple"),
+    (25413, "Old Lion's Court"),
+];
+
+/// Returns the name of the boss with the given species id.
+pub fn boss_name(species: u32) -> Option<&'static str> {
+    BOSSES
+        .iter()
+        .find(|(id, _)| *id == species)
+        .map(|(_, name)| *name)
+}
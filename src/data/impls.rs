@@ -23,6 +23,19 @@ impl DefData {
 }
 
 impl Rarity {
+    /// Returns the name of the [`Rarity`].
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Basic => "Basic",
+            Self::Fine => "Fine",
+            Self::Masterwork => "Masterwork",
+            Self::Rare => "Rare",
+            Self::Exotic => "Exotic",
+            Self::Ascended => "Ascended",
+            Self::Legendary => "Legendary",
+        }
+    }
+
     /// Returns the color associated with the [`Rarity`].
     pub fn color(&self) -> Option<Color> {
         match self {
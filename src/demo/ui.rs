@@ -1,6 +1,6 @@
 use super::Demo;
 use crate::{
-    buff_ui::render_buff_tooltip,
+    buff_ui::buff_combo,
     combo_ui::render_prof_select,
     data::Definitions,
     tracking::{
@@ -13,7 +13,7 @@ use arc_util::{
     ui::{render, Component, Hideable, Windowable},
 };
 use arcdps::{
-    imgui::{Selectable, StyleColor, TableColumnSetup, Ui},
+    imgui::{TableColumnSetup, Ui},
     Profession, Specialization,
 };
 use std::borrow::Cow;
@@ -26,47 +26,22 @@ const SPECIAL_BUFFS: [BuffState<u32>; 3] =
 impl Demo {
     fn render_combo<'b>(
         ui: &Ui,
-        defs: &Definitions,
+        defs: &'b Definitions,
         label: impl AsRef<str>,
         all: &'b [BuffState<u32>],
         current: &mut BuffState<u32>,
-        item_label: impl Fn(&BuffState<u32>) -> Cow<str>,
+        item_label: impl Fn(&BuffState<u32>) -> Cow<str> + 'b,
     ) -> Option<&'b BuffState<u32>> {
-        let mut result = None;
-        if let Some(_token) = ui.begin_combo(label, item_label(current)) {
-            for entry in all {
-                let selected = entry == current;
-                let data = match entry {
-                    BuffState::Some(id) => defs.definition(*id).and_then(|def| def.data()),
-                    _ => None,
-                };
-
-                // apply color to selectable
-                let style = data
-                    .and_then(|data| data.rarity.color())
-                    .map(|color| ui.push_style_color(StyleColor::Text, color));
-                if Selectable::new(item_label(entry))
-                    .selected(selected)
-                    .build(ui)
-                {
-                    result = Some(entry);
-                }
-                drop(style);
-
-                // handle focus
-                if selected {
-                    ui.set_item_default_focus();
-                }
-
-                // tooltip
-                if ui.is_item_hovered() {
-                    if let Some(buff) = data {
-                        render_buff_tooltip(ui, buff);
-                    }
-                }
-            }
-        }
-        result
+        let preview = item_label(current).into_owned();
+        buff_combo(
+            label.as_ref(),
+            move |entry: &&BuffState<u32>| item_label(entry),
+            |entry| match entry {
+                BuffState::Some(id) => defs.definition(*id).and_then(|def| def.data()),
+                _ => None,
+            },
+        )
+        .build(ui, preview, all, |entry| **entry == *current)
     }
 }
 
@@ -250,7 +225,7 @@ impl Component<Props<'_>> for Demo {
         }
 
         // render children
-        self.reminder.render(ui, ());
+        self.reminder.render(ui, defs);
         self.tracker.render(ui, (defs, custom));
     }
 }
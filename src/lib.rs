@@ -12,7 +12,7 @@ mod util;
 mod demo;
 
 use arcdps::{
-    extras::{ExtrasAddonInfo, UserInfoIter},
+    extras::{message::ChatMessageInfo, ExtrasAddonInfo, UserInfoIter},
     imgui::Ui,
     Agent, Event,
 };
@@ -31,6 +31,7 @@ arcdps::export! {
     wnd_filter,
     extras_init,
     extras_squad_update,
+    extras_chat_message,
 }
 
 fn init() -> Result<(), String> {
@@ -77,3 +78,7 @@ fn extras_init(addon_info: ExtrasAddonInfo, account_name: Option<&str>) {
 fn extras_squad_update(users: UserInfoIter) {
     Plugin::lock().extras_squad_update(users)
 }
+
+fn extras_chat_message(message: &ChatMessageInfo) {
+    Plugin::lock().extras_chat_message(message)
+}
@@ -1,11 +1,21 @@
 use super::{ExtrasState, Plugin};
-use crate::{data::BuffKind, tracking::Sorting};
+use crate::{
+    data::BuffKind,
+    tracking::{
+        squad::{self, SquadMember},
+        Sorting,
+    },
+};
 use arc_util::{
     api::delta_time,
     tracking::{Entry, Player},
+    ui::Hideable,
 };
 use arcdps::{
-    extras::{ExtrasAddonInfo, UserInfo, UserInfoIter, UserRole},
+    extras::{
+        message::{ChannelType, ChatMessageInfo},
+        ExtrasAddonInfo, UserInfoIter, UserRole,
+    },
     Activation, Agent, BuffRemove, Event, StateChange,
 };
 use log::{debug, info, log_enabled, Level};
@@ -69,7 +79,7 @@ impl Plugin {
                         let plugin = guard.as_mut();
                         let target_id = event.src_agent;
                         debug!("Log for id {} ended", target_id);
-                        plugin.reminder.end_encounter(&plugin.tracker.players);
+                        plugin.reminder.end_encounter(&plugin.tracker);
                     }
 
                     StateChange::None | StateChange::ApiDelayed | StateChange::BuffInitial => {
@@ -111,7 +121,7 @@ impl Plugin {
                     let plugin = guard.as_mut();
                     plugin
                         .reminder
-                        .update_pending_check(&plugin.tracker.players, event.time);
+                        .update_pending_check(&plugin.tracker, event.time);
                 }
             } else {
                 // check for player tracking change
@@ -143,7 +153,15 @@ impl Plugin {
         event_id: u64,
     ) {
         let statechange = event.get_statechange();
+        let account = self.tracker.account.as_deref();
         if let Some(Entry { player, data }) = self.tracker.players.player_mut(player_id) {
+            let is_self = player.is_self || account == Some(player.account.as_str());
+
+            // track initial buffs on self for pending check
+            if statechange == StateChange::BuffInitial && is_self {
+                self.reminder.self_buff_initial(event.time);
+            }
+
             if let Some(remind) = self.reminder.custom(buff_id) {
                 debug!(
                     "Custom {} apply id {} time {} statechange {}",
@@ -177,7 +195,7 @@ impl Plugin {
                                 info!("Unknown Food {} applied to {}", buff_id, player.character);
                             }
 
-                            if player.is_self {
+                            if is_self {
                                 self.reminder.self_buff_apply(buff_id);
                             }
 
@@ -202,7 +220,7 @@ impl Plugin {
                                 );
                             }
 
-                            if player.is_self {
+                            if is_self {
                                 self.reminder.self_buff_apply(buff_id);
                             }
 
@@ -229,7 +247,10 @@ impl Plugin {
     ) {
         let statechange = event.get_statechange();
         let buffremove = event.get_buffremove();
+        let account = self.tracker.account.as_deref();
         if let Some(Entry { player, data }) = self.tracker.players.player_mut(player_id) {
+            let is_self = player.is_self || account == Some(player.account.as_str());
+
             if let Some(remind) = self.reminder.custom(buff_id) {
                 debug!(
                     "Custom {} remove id {} time {} statechange {} kind {}",
@@ -248,7 +269,7 @@ impl Plugin {
                     );
 
                     // check for custom buff running out
-                    if player.is_self {
+                    if is_self {
                         self.reminder.self_custom_remove(data);
                     }
                 }
@@ -270,7 +291,7 @@ impl Plugin {
                             }
 
                             // check for food running out
-                            if player.is_self {
+                            if is_self {
                                 self.reminder.self_food_remove(data);
                             }
 
@@ -296,7 +317,7 @@ impl Plugin {
                             }
 
                             // check for utility running out
-                            if player.is_self {
+                            if is_self {
                                 self.reminder.self_util_remove(data);
                             }
 
@@ -313,40 +334,112 @@ impl Plugin {
     }
 
     /// Handles initialization from unofficial extras.
-    pub fn extras_init(&mut self, extras_info: ExtrasAddonInfo, _account_name: Option<&str>) {
+    pub fn extras_init(&mut self, extras_info: ExtrasAddonInfo, account_name: Option<&str>) {
         self.extras = if extras_info.is_compatible() {
             ExtrasState::Found
         } else {
             ExtrasState::Incompatible
         };
+
+        if let Some(name) = account_name {
+            info!("Own account {}", name);
+            self.tracker.set_account(name);
+        }
     }
 
     /// Handles a squad update from unofficial extras.
     pub fn extras_squad_update(&mut self, users: UserInfoIter) {
+        let mut ready_check = false;
+
         for user in users {
-            if let UserInfo {
-                role: UserRole::SquadLeader | UserRole::Lieutenant | UserRole::Member,
-                ..
-            } = user
-            {
-                if let Some(name) = user.account_name() {
+            if let Some(name) = user.account_name() {
+                if let UserRole::SquadLeader | UserRole::Lieutenant | UserRole::Member = user.role {
+                    let subgroup = user.subgroup as usize + 1;
+
+                    // check for ready check started by squad leader
+                    let previous = self.tracker.squad.get(name);
+                    if squad::starts_ready_check(previous, user.role, user.ready_status) {
+                        ready_check = true;
+                    }
+
+                    self.tracker.squad.insert(
+                        name.to_string(),
+                        SquadMember::new(user.role, subgroup, user.ready_status),
+                    );
+
                     if let Some(Entry { player, .. }) = self
                         .tracker
                         .players
                         .iter_mut()
                         .find(|entry| entry.player.account == name)
                     {
-                        player.subgroup = user.subgroup as usize + 1;
+                        player.subgroup = subgroup;
 
                         debug!(
                             "Updated subgroup {} for {}",
                             player.subgroup, player.character
                         );
                     }
+                } else {
+                    // user left squad
+                    self.tracker.squad.remove(name);
                 }
             }
         }
 
         self.tracker.refresh_sort_if(Sorting::Sub);
+
+        if ready_check {
+            self.ready_check();
+        }
+    }
+
+    /// Handles a chat message from unofficial extras.
+    pub fn extras_chat_message(&mut self, message: &ChatMessageInfo) {
+        let settings = &self.reminder.settings;
+        if settings.chat_command
+            && !settings.chat_keyword.is_empty()
+            && matches!(
+                message.channel_type,
+                ChannelType::Squad | ChannelType::Party
+            )
+        {
+            let is_command = message
+                .text
+                .split_whitespace()
+                .next()
+                .map_or(false, |word| {
+                    word.eq_ignore_ascii_case(&settings.chat_keyword)
+                });
+
+            if is_command {
+                let account = message.account_name;
+                let allowed = self
+                    .tracker
+                    .squad
+                    .get(account)
+                    .map_or(false, |member| settings.chat_role.allows(member.role));
+
+                if allowed {
+                    info!("Chat command from {}", account);
+                    if self.reminder.chat_command(&self.tracker) {
+                        self.tracker.set_visibility(true);
+                    }
+                } else {
+                    debug!("Ignored chat command from {}", account);
+                }
+            }
+        }
+    }
+
+    /// Handles a ready check started by the squad leader.
+    fn ready_check(&mut self) {
+        info!("Ready check started");
+
+        self.reminder.ready_check(&self.tracker);
+
+        if self.tracker.settings.ready_check_open {
+            self.tracker.set_visibility(true);
+        }
     }
 }
@@ -28,6 +28,9 @@ const SETTINGS_FILE: &str = "arcdps_food_reminder.json";
 /// Definitions file name.
 const DEFINITIONS_FILE: &str = "arcdps_food_reminder_definitions.json";
 
+/// Build library file name.
+const LIBRARY_FILE: &str = "arcdps_food_reminder_builds.json";
+
 /// Main plugin instance.
 // FIXME: a single mutex for the whole thing is potentially inefficient
 static PLUGIN: Lazy<Mutex<Plugin>> = Lazy::new(|| Mutex::new(Plugin::new()));
@@ -44,6 +47,9 @@ pub struct Plugin {
     /// State of loading custom definitions.
     defs_state: Result<(), LoadError>,
 
+    /// State of loading the build library.
+    library_state: Result<(), LoadError>,
+
     /// Food reminder.
     reminder: Reminder,
 
@@ -65,6 +71,7 @@ impl Plugin {
             extras: ExtrasState::Missing,
             defs: Definitions::with_defaults(),
             defs_state: Err(LoadError::NotFound),
+            library_state: Err(LoadError::NotFound),
             reminder: Reminder::new(),
 
             tracker: Window::new(
@@ -158,6 +165,25 @@ impl Plugin {
                 info!("Removed definitions from old version");
             }
         }
+
+        // load build library
+        self.load_library();
+    }
+
+    /// Loads the build library file.
+    fn load_library(&mut self) {
+        if let Some(library_path) = Settings::config_path(LIBRARY_FILE) {
+            self.library_state = self.tracker.builds.try_load_library(&library_path);
+
+            match self.library_state {
+                Ok(()) => info!("Loaded build library from \"{}\"", library_path.display()),
+                Err(LoadError::NotFound) => self.tracker.builds.library.clear(),
+                Err(_) => warn!(
+                    "Failed to load build library from \"{}\"",
+                    library_path.display()
+                ),
+            }
+        }
     }
 
     /// Unloads the plugin.
@@ -1,9 +1,13 @@
 use super::Plugin;
 use crate::{
     combo_ui::render_enum_combo,
-    data::{Definitions, LoadError},
+    data::{boss_name, Definitions, LoadError},
     plugin::{ExtrasState, DEFINITIONS_FILE},
-    reminder::custom::CustomReminder,
+    reminder::{
+        custom::CustomReminder,
+        rules::{EncounterRule, RuleKind, RuleTarget},
+        settings::Position,
+    },
 };
 use arc_util::{
     colors::{GREEN, GREY, RED, YELLOW},
@@ -20,8 +24,14 @@ use std::time::Duration;
 impl Plugin {
     /// Callback for standalone UI creation.
     pub fn render_windows(&mut self, ui: &Ui, not_loading: bool) {
+        // check for map changes
+        self.reminder.update_map(&self.tracker);
+
+        // update buffs expected by the assigned build
+        self.reminder.expected = self.tracker.expected_buffs();
+
         // reminder, log & demo render always
-        self.reminder.render(ui, ());
+        self.reminder.render(ui, &self.defs);
 
         #[cfg(feature = "demo")]
         self.demo
@@ -61,6 +71,15 @@ impl Plugin {
             &mut self.tracker.settings.save_chars,
         );
 
+        // tracker ready check
+        ui.checkbox(
+            "Open tracker on ready check",
+            &mut self.tracker.settings.ready_check_open,
+        );
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Requires unofficial extras.");
+        }
+
         // tracker hotkey
         render::input_key(
             ui,
@@ -80,7 +99,7 @@ impl Plugin {
         });
         if ui.is_item_hovered() {
             ui.tooltip_text(
-                "Unofficial extras allows for more frequent updates on player subgroups.",
+                "Unofficial extras allows for more frequent updates on player subgroups and ready checks.",
             );
         }
 
@@ -107,6 +126,37 @@ impl Plugin {
             "Remind during encounter",
             &mut self.reminder.settings.during_encounter,
         );
+        ui.checkbox(
+            "Remind when entering an instance",
+            &mut self.reminder.settings.instance_enter,
+        );
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Check buffs when entering a raid, fractal or strike mission map.");
+        }
+        ui.checkbox(
+            "Remind on ready check",
+            &mut self.reminder.settings.ready_check,
+        );
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Check buffs when the squad leader starts a ready check.\nRequires unofficial extras.");
+        }
+
+        // chat command
+        ui.checkbox(
+            "Remind on squad chat command",
+            &mut self.reminder.settings.chat_command,
+        );
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Check buffs and open the tracker when the keyword is posted in squad or party chat.\nRequires unofficial extras.");
+        }
+        if self.reminder.settings.chat_command {
+            ui.set_next_item_width(input_width);
+            ui.input_text("Chat keyword", &mut self.reminder.settings.chat_keyword)
+                .build();
+
+            ui.set_next_item_width(input_width);
+            render_enum_combo(ui, "Minimum role", &mut self.reminder.settings.chat_role);
+        }
 
         ui.checkbox(
             "Restrict reminders for encounters to bosses",
@@ -126,6 +176,32 @@ impl Plugin {
             );
         }
 
+        // squad alert
+        ui.checkbox(
+            "Alert for squad members missing Food/Utility",
+            &mut self.reminder.settings.squad_alert,
+        );
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Lists players missing Food or Utility on encounter start.");
+        }
+        if self.reminder.settings.squad_alert {
+            let mut threshold = self.reminder.settings.squad_threshold as i32;
+            ui.set_next_item_width(input_width);
+            if ui
+                .input_int("Minimum players missing", &mut threshold)
+                .build()
+            {
+                self.reminder.settings.squad_threshold = threshold.max(1) as usize;
+            }
+
+            ui.set_next_item_width(input_width);
+            render_enum_combo(
+                ui,
+                "Squad alert scope",
+                &mut self.reminder.settings.squad_scope,
+            );
+        }
+
         // reminder duration
         let mut dura = self.reminder.settings.duration.as_millis() as i32;
         ui.set_next_item_width(input_width);
@@ -142,23 +218,34 @@ impl Plugin {
         }
 
         // reminder position
-        let mut pos = self.reminder.settings.position * 100.0;
-        ui.set_next_item_width(input_width);
-        if render::input_float_with_format(
-            "Position (%)",
-            &mut pos,
-            1.0,
-            10.0,
-            "%.1f",
-            InputTextFlags::empty(),
-        ) {
-            self.reminder.settings.position = pos / 100.0;
+        let settings = &mut self.reminder.settings;
+        ui.checkbox("Separate positions", &mut settings.separate_positions);
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Use separate positions for Food, Utility & custom reminders.");
+        }
+        if settings.separate_positions {
+            Self::render_position_input(ui, "Food", &mut settings.food_position, input_width);
+            Self::render_position_input(ui, "Utility", &mut settings.util_position, input_width);
+            Self::render_position_input(ui, "Custom", &mut settings.custom_position, input_width);
+        } else {
+            Self::render_position_input(ui, "Position", &mut settings.position, input_width);
+        }
+
+        // place button
+        let place = if self.reminder.is_placing() {
+            "Done placing"
+        } else {
+            "Place reminder"
+        };
+        if ui.button(place) {
+            self.reminder.toggle_placing();
         }
         if ui.is_item_hovered() {
-            ui.tooltip_text("Vertical position of the reminder displayed on screen.");
+            ui.tooltip_text("Drag the reminder to the desired position.");
         }
 
         // test button
+        ui.same_line_with_spacing(0.0, SPACING);
         if ui.button("Test reminder") {
             self.reminder.trigger_all();
         }
@@ -206,6 +293,65 @@ impl Plugin {
         ui.same_line();
         self.reminder.render_custom_reset(ui);
 
+        ui.spacing();
+        ui.spacing();
+        ui.text_colored(grey, "Encounter rules");
+        if ui.is_item_hovered() {
+            ui.tooltip_text(
+                "Allow or block reminders for specific bosses.\nIf a reminder has allowed bosses, it only triggers for those.",
+            );
+        }
+
+        let current = self.reminder.current_encounter();
+        let rules = &mut self.reminder.settings.rules;
+        let mut action = Action::new();
+        let len = rules.len();
+
+        for (i, rule) in rules.iter_mut().enumerate() {
+            ui.set_next_item_width(70.0);
+            render_enum_combo(ui, format!("##rule-kind-{i}"), &mut rule.kind);
+
+            ui.same_line();
+            ui.set_next_item_width(70.0);
+            render_enum_combo(ui, format!("##rule-target-{i}"), &mut rule.target);
+
+            let mut species = rule.species.try_into().unwrap_or(0);
+            ui.same_line();
+            ui.set_next_item_width(render::ch_width(ui, 7));
+            if ui
+                .input_int(format!("##rule-species-{i}"), &mut species)
+                .step(0)
+                .build()
+            {
+                rule.species = species.max(0) as u32;
+            }
+
+            ui.same_line();
+            match boss_name(rule.species) {
+                Some(name) => ui.text(name),
+                None => ui.text_colored(grey, "Unknown"),
+            }
+
+            // action buttons
+            ui.same_line();
+            action.render_buttons(ui, "rule-actions", i, len);
+        }
+        action.perform(rules);
+
+        if ui.button("Add##rule") {
+            rules.push(EncounterRule::empty());
+        }
+        if let Some(species) = current.and_then(|id| u32::try_from(id).ok()) {
+            ui.same_line();
+            if ui.button("Add current encounter") {
+                rules.push(EncounterRule::new(
+                    species,
+                    RuleTarget::All,
+                    RuleKind::Block,
+                ));
+            }
+        }
+
         ui.spacing();
         ui.spacing();
 
@@ -244,6 +390,26 @@ impl Plugin {
             self.defs_state = Err(LoadError::NotFound);
         }
 
+        ui.spacing();
+        ui.spacing();
+
+        ui.text_colored(grey, "Build library");
+        ui.text("Status:");
+        ui.same_line();
+        match self.library_state {
+            Ok(()) => ui.text_colored(
+                green,
+                format!("Loaded {} builds", self.tracker.builds.library.len()),
+            ),
+            Err(LoadError::NotFound) => ui.text_colored(yellow, "Not found"),
+            Err(LoadError::FailedToRead) => ui.text_colored(red, "Failed to read file"),
+            Err(LoadError::InvalidJSON) => ui.text_colored(red, "Failed to parse JSON"),
+        }
+
+        if ui.button("Reload build library") {
+            self.load_library();
+        }
+
         ui.spacing();
         ui.separator();
         ui.spacing();
@@ -258,6 +424,44 @@ impl Plugin {
         self.refresh_demo_settings();
     }
 
+    /// Renders inputs for a reminder position.
+    fn render_position_input(ui: &Ui, label: &str, position: &mut Position, input_width: f32) {
+        let width = 0.5 * input_width;
+
+        let mut x = position.x * 100.0;
+        ui.set_next_item_width(width);
+        if render::input_float_with_format(
+            &format!("##{label}-x"),
+            &mut x,
+            1.0,
+            10.0,
+            "%.1f",
+            InputTextFlags::empty(),
+        ) {
+            position.x = (x / 100.0).clamp(0.0, 1.0);
+        }
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Horizontal position of the reminder displayed on screen.");
+        }
+
+        let mut y = position.y * 100.0;
+        ui.same_line();
+        ui.set_next_item_width(width);
+        if render::input_float_with_format(
+            &format!("{label} (%)"),
+            &mut y,
+            1.0,
+            10.0,
+            "%.1f",
+            InputTextFlags::empty(),
+        ) {
+            position.y = (y / 100.0).clamp(0.0, 1.0);
+        }
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Vertical position of the reminder displayed on screen.");
+        }
+    }
+
     /// Callback for ArcDPS option checkboxes.
     pub fn render_window_options(ui: &Ui, option_name: Option<&str>) -> bool {
         if option_name.is_none() {
@@ -1,4 +1,4 @@
-use crate::data::{FRACTAL_MAPS, RAID_MAPS};
+use crate::data::{FRACTAL_MAPS, RAID_MAPS, STRIKE_MAPS};
 use serde::{Deserialize, Serialize};
 use strum::{AsRefStr, EnumIter};
 
@@ -84,6 +84,7 @@ pub enum GameMode {
     All,
     Raid,
     Fractal,
+    Strike,
 }
 
 impl GameMode {
@@ -93,6 +94,14 @@ impl GameMode {
             GameMode::All => true,
             GameMode::Raid => RAID_MAPS.contains(&map_id),
             GameMode::Fractal => FRACTAL_MAPS.contains(&map_id),
+            GameMode::Strike => STRIKE_MAPS.contains(&map_id),
         }
     }
+
+    /// Checks whether the map id is an instance of any [`GameMode`].
+    pub fn is_instance(map_id: u32) -> bool {
+        [GameMode::Raid, GameMode::Fractal, GameMode::Strike]
+            .iter()
+            .any(|mode| mode.is_map(map_id))
+    }
 }
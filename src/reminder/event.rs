@@ -1,13 +1,37 @@
-use super::{Encounter, Reminder};
+use super::{
+    custom::GameMode,
+    rules::{EncounterRule, RuleTarget},
+    settings::SquadScope,
+    Encounter, Reminder,
+};
 use crate::{
     data::{DIMINISHED, MALNOURISHED},
-    tracking::buff::{BuffState, Buffs},
+    tracking::{
+        buff::{BuffState, Buffs},
+        Tracker,
+    },
 };
-use arc_util::tracking::CachedTracker;
 use log::debug;
+use std::time::{Duration, Instant};
+
+/// Time (ms) without buff initial events on self after which the initial burst is considered ended.
+const INITIAL_BURST_GAP: u64 = 50;
+
+/// Maximum time (ms) since the encounter start for the buff check to trigger.
+///
+/// Used as fallback when no buff initial events on self are received.
+/// Matches the previous fixed delay, so the check is never later than before.
+const CHECK_TIMEOUT: u64 = 250;
 
-/// Minimum time (ms) since the encounter start for the buff check to trigger.
-const CHECK_TIME_DIFF: u64 = 250;
+/// Delay after entering an instance for the buff check to trigger.
+///
+/// Allows player tracking & buffs to update after the map change.
+const MAP_CHECK_DELAY: Duration = Duration::from_secs(3);
+
+/// Minimum time between two accepted chat commands.
+///
+/// Prevents chat spam from repeatedly triggering the check.
+const CHAT_COMMAND_COOLDOWN: Duration = Duration::from_secs(10);
 
 impl Reminder {
     /// Returns the current encounter id.
@@ -21,6 +45,7 @@ impl Reminder {
             target_id,
             start_time,
             pending_check: self.settings.encounter_start,
+            last_initial: None,
         });
     }
 
@@ -34,24 +59,98 @@ impl Reminder {
     }
 
     /// Handles encounter end.
-    pub fn end_encounter(&mut self, players: &CachedTracker<Buffs>) {
+    pub fn end_encounter(&mut self, tracker: &Tracker) {
         if self.settings.encounter_end {
-            self.check_self_all(players);
+            self.check_self_encounter(tracker);
         }
         self.encounter = None;
     }
 
+    /// Handles a buff initial event on self.
+    pub fn self_buff_initial(&mut self, time: u64) {
+        if let Some(encounter) = &mut self.encounter {
+            encounter.last_initial = Some(time);
+        }
+    }
+
     /// Updates pending buff check.
-    pub fn update_pending_check(&mut self, players: &CachedTracker<Buffs>, time: u64) {
+    ///
+    /// The check triggers once the burst of buff initial events on self has ended.
+    /// Falls back to a timeout if no buff initial events on self were received.
+    pub fn update_pending_check(&mut self, tracker: &Tracker, time: u64) {
         // handle pending check
         if let Some(encounter) = &mut self.encounter {
-            if encounter.pending_check && time >= encounter.start_time + CHECK_TIME_DIFF {
+            let burst_ended = matches!(
+                encounter.last_initial,
+                Some(last) if time >= last + INITIAL_BURST_GAP
+            );
+            let timed_out = time >= encounter.start_time + CHECK_TIMEOUT;
+
+            if encounter.pending_check && (burst_ended || timed_out) {
+                debug!(
+                    "Pending check at {} after start {}, last initial {:?}",
+                    time, encounter.start_time, encounter.last_initial
+                );
                 encounter.pending_check = false;
-                self.check_self_all(players);
+                self.check_self_encounter(tracker);
+                self.check_squad(tracker);
+            }
+        }
+    }
+
+    /// Updates the current map from MumbleLink.
+    ///
+    /// Schedules a buff check when entering an instance.
+    pub fn update_map(&mut self, tracker: &Tracker) {
+        if let Some(mumble) = &self.mumble {
+            let map_id = mumble.read().context.map_id;
+            if map_id != self.map_id {
+                debug!("Map changed from {} to {}", self.map_id, map_id);
+                self.map_id = map_id;
+                self.map_check = if self.settings.instance_enter && GameMode::is_instance(map_id) {
+                    Some(Instant::now())
+                } else {
+                    None
+                };
+            }
+        }
+
+        // handle pending check
+        if let Some(time) = self.map_check {
+            if time.elapsed() >= MAP_CHECK_DELAY && tracker.self_entry().is_some() {
+                debug!("Checking self after entering instance {}", self.map_id);
+                self.map_check = None;
+                self.check_self_all(tracker);
             }
         }
     }
 
+    /// Handles a ready check.
+    pub fn ready_check(&mut self, tracker: &Tracker) {
+        if self.settings.ready_check {
+            debug!("Checking self on ready check");
+            self.check_self_all(tracker);
+        }
+    }
+
+    /// Handles a chat command.
+    ///
+    /// Returns `false` if the command was ignored due to the cooldown.
+    pub fn chat_command(&mut self, tracker: &Tracker) -> bool {
+        if self
+            .chat_command_time
+            .map_or(false, |time| time.elapsed() < CHAT_COMMAND_COOLDOWN)
+        {
+            debug!("Ignored chat command during cooldown");
+            return false;
+        }
+        self.chat_command_time = Some(Instant::now());
+
+        debug!("Checking self on chat command");
+        self.check_self_all(tracker);
+        true
+    }
+
     /// Handles a buff apply to self.
     pub fn self_buff_apply(&mut self, buff_id: u32) {
         if self.settings.always_mal_dim {
@@ -65,37 +164,92 @@ impl Reminder {
 
     /// Handles a food remove from self.
     pub fn self_food_remove(&mut self, buffs: &Buffs) {
-        if self.settings.during_encounter {
+        if self.settings.during_encounter && self.can_remind(RuleTarget::Food) {
             self.check_food(buffs);
         }
     }
 
     /// Handles an utility remove from self.
     pub fn self_util_remove(&mut self, buffs: &Buffs) {
-        if self.settings.during_encounter {
+        if self.settings.during_encounter && self.can_remind(RuleTarget::Util) {
             self.check_util(buffs);
         }
     }
 
     /// Handles a custom tracked buff remove from self.
     pub fn self_custom_remove(&mut self, buffs: &Buffs) {
-        if self.settings.during_encounter {
+        if self.settings.during_encounter && self.can_remind(RuleTarget::Custom) {
             self.check_custom(buffs);
         }
     }
 
-    /// Whether reminders can be triggered.
-    fn can_remind(&self) -> bool {
+    /// Whether reminders of the given type can be triggered in the current encounter.
+    fn can_remind(&self, target: RuleTarget) -> bool {
         match &self.encounter {
-            Some(encounter) if self.settings.only_bosses => encounter.target_id > 1,
-            Some(_) => true,
+            Some(encounter) => {
+                (!self.settings.only_bosses || encounter.target_id > 1)
+                    && EncounterRule::is_allowed(&self.settings.rules, encounter.target_id, target)
+            }
             None => false,
         }
     }
 
+    /// Performs a check for all reminders allowed in the current encounter.
+    fn check_self_encounter(&mut self, tracker: &Tracker) {
+        if let Some(player) = tracker.self_entry() {
+            if self.can_remind(RuleTarget::Food) {
+                self.check_food(&player.data);
+            }
+            if self.can_remind(RuleTarget::Util) {
+                self.check_util(&player.data);
+            }
+            if self.can_remind(RuleTarget::Custom) {
+                self.check_custom(&player.data);
+            }
+        }
+    }
+
+    /// Checks for squad members missing food or utility.
+    fn check_squad(&mut self, tracker: &Tracker) {
+        if self.settings.squad_alert && self.can_remind(RuleTarget::All) {
+            let subgroup = tracker.self_entry().map(|entry| entry.player.subgroup);
+            let missing = tracker
+                .players
+                .iter()
+                .filter(|entry| !tracker.is_self(&entry.player))
+                .filter(|entry| match self.settings.squad_scope {
+                    SquadScope::Subgroup => Some(entry.player.subgroup) == subgroup,
+                    SquadScope::Squad => true,
+                })
+                .filter_map(|entry| {
+                    let food = matches!(
+                        entry.data.food.state,
+                        BuffState::None | BuffState::Some(MALNOURISHED)
+                    );
+                    let util = matches!(
+                        entry.data.util.state,
+                        BuffState::None | BuffState::Some(DIMINISHED)
+                    );
+                    let missing = match (food, util) {
+                        (true, true) => "Food & Utility",
+                        (true, false) => "Food",
+                        (false, true) => "Utility",
+                        (false, false) => return None,
+                    };
+                    Some(format!("{} ({})", entry.player.character, missing))
+                })
+                .collect::<Vec<_>>();
+
+            debug!("Checking squad: {} players missing buffs", missing.len());
+            if !missing.is_empty() && missing.len() >= self.settings.squad_threshold {
+                self.trigger_squad(missing);
+            }
+        }
+    }
+
     /// Performs a check for all reminders.
-    fn check_self_all(&mut self, players: &CachedTracker<Buffs>) {
-        if let Some(player) = players.get_self() {
+    fn check_self_all(&mut self, tracker: &Tracker) {
+        if let Some(player) = tracker.self_entry() {
             self.check_food(&player.data);
             self.check_util(&player.data);
             self.check_custom(&player.data);
@@ -104,35 +258,299 @@ impl Reminder {
 
     /// Checks for missing food buff.
     fn check_food(&mut self, buffs: &Buffs) {
-        if self.can_remind() {
-            let Buffs { food, .. } = buffs;
-            debug!("Checking food on self: {:?}", food.state);
-            if let BuffState::None | BuffState::Some(MALNOURISHED) = food.state {
-                self.trigger_food();
-            }
+        let Buffs { food, .. } = buffs;
+        debug!("Checking food on self: {:?}", food.state);
+        if let BuffState::None | BuffState::Some(MALNOURISHED) = food.state {
+            self.trigger_food();
         }
     }
 
     /// Checks for missing utility buff.
     fn check_util(&mut self, buffs: &Buffs) {
-        if self.can_remind() {
-            let Buffs { util, .. } = buffs;
-            debug!("Checking utility on self: {:?}", util.state);
-            if let BuffState::None | BuffState::Some(DIMINISHED) = util.state {
-                self.trigger_util();
-            }
+        let Buffs { util, .. } = buffs;
+        debug!("Checking utility on self: {:?}", util.state);
+        if let BuffState::None | BuffState::Some(DIMINISHED) = util.state {
+            self.trigger_util();
         }
     }
 
     /// Checks for missing custom tracked buffs.
     fn check_custom(&mut self, buffs: &Buffs) {
-        if self.can_remind() {
-            for (id, buff) in &buffs.custom {
-                debug!("Checking custom buff {} on self: {:?}", id, buff.state);
-                if let BuffState::None = buff.state {
-                    self.trigger_custom(*id);
-                }
+        for (id, buff) in &buffs.custom {
+            debug!("Checking custom buff {} on self: {:?}", id, buff.state);
+            if let BuffState::None = buff.state {
+                self.trigger_custom(*id);
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tracking::buff::TrackedBuff;
+    use arc_util::tracking::Player;
+    use arcdps::{Profession, Specialization};
+
+    const BOSS: u64 = 15438;
+    const START: u64 = 1000;
+
+    fn tracker_with_self(food: BuffState<u32>, util: BuffState<u32>) -> Tracker {
+        let mut tracker = Tracker::new();
+        tracker.players.add_player_default(Player::new(
+            0,
+            0,
+            "char",
+            "acc",
+            true,
+            Profession::Guardian,
+            Specialization::Firebrand,
+            1,
+        ));
+        let entry = tracker.players.player_mut(0).unwrap();
+        entry.data.food = TrackedBuff::new(food);
+        entry.data.util = TrackedBuff::new(util);
+        tracker
+    }
+
+    fn add_player(
+        tracker: &mut Tracker,
+        id: usize,
+        subgroup: usize,
+        food: BuffState<u32>,
+        util: BuffState<u32>,
+    ) {
+        tracker.players.add_player_default(Player::new(
+            id,
+            0,
+            format!("char{id}"),
+            format!("acc{id}"),
+            false,
+            Profession::Guardian,
+            Specialization::Firebrand,
+            subgroup,
+        ));
+        let entry = tracker.players.player_mut(id).unwrap();
+        entry.data.food = TrackedBuff::new(food);
+        entry.data.util = TrackedBuff::new(util);
+    }
+
+    fn squad_reminder() -> Reminder {
+        let mut reminder = Reminder::new();
+        reminder.settings.squad_alert = true;
+        reminder.start_encounter(BOSS, START);
+        reminder
+    }
+
+    fn triggered(reminder: &Reminder) -> (bool, bool) {
+        (
+            reminder.food_trigger.is_some(),
+            reminder.util_trigger.is_some(),
+        )
+    }
+
+    #[test]
+    fn waits_for_initial_burst() {
+        let tracker = tracker_with_self(BuffState::None, BuffState::None);
+        let mut reminder = Reminder::new();
+        reminder.start_encounter(BOSS, START);
+
+        // burst of buff initial events
+        reminder.self_buff_initial(START);
+        reminder.update_pending_check(&tracker, START);
+        reminder.self_buff_initial(START + 10);
+        reminder.update_pending_check(&tracker, START + 20);
+        assert_eq!(triggered(&reminder), (false, false));
+
+        // burst ended
+        reminder.update_pending_check(&tracker, START + 10 + INITIAL_BURST_GAP);
+        assert_eq!(triggered(&reminder), (true, true));
+    }
+
+    #[test]
+    fn checks_early_after_burst() {
+        let tracker = tracker_with_self(BuffState::None, BuffState::Some(1234));
+        let mut reminder = Reminder::new();
+        reminder.start_encounter(BOSS, START);
+
+        reminder.self_buff_initial(START);
+        reminder.update_pending_check(&tracker, START + INITIAL_BURST_GAP);
+        assert!(START + INITIAL_BURST_GAP < START + CHECK_TIMEOUT);
+        assert_eq!(triggered(&reminder), (true, false));
+    }
+
+    #[test]
+    fn falls_back_to_timeout() {
+        let tracker = tracker_with_self(BuffState::None, BuffState::None);
+        let mut reminder = Reminder::new();
+        reminder.start_encounter(BOSS, START);
+
+        // no buff initial events on self
+        reminder.update_pending_check(&tracker, START + 100);
+        reminder.update_pending_check(&tracker, START + CHECK_TIMEOUT - 1);
+        assert_eq!(triggered(&reminder), (false, false));
+
+        reminder.update_pending_check(&tracker, START + CHECK_TIMEOUT);
+        assert_eq!(triggered(&reminder), (true, true));
+    }
+
+    #[test]
+    fn no_trigger_with_buffs() {
+        let tracker = tracker_with_self(BuffState::Some(1234), BuffState::Some(5678));
+        let mut reminder = Reminder::new();
+        reminder.start_encounter(BOSS, START);
+
+        reminder.self_buff_initial(START);
+        reminder.update_pending_check(&tracker, START + INITIAL_BURST_GAP);
+        assert_eq!(triggered(&reminder), (false, false));
+        assert!(!reminder.encounter.as_ref().unwrap().pending_check);
+    }
+
+    #[test]
+    fn no_check_when_disabled() {
+        let tracker = tracker_with_self(BuffState::None, BuffState::None);
+        let mut reminder = Reminder::new();
+        reminder.settings.encounter_start = false;
+        reminder.start_encounter(BOSS, START);
+
+        reminder.self_buff_initial(START);
+        reminder.update_pending_check(&tracker, START + CHECK_TIMEOUT);
+        assert_eq!(triggered(&reminder), (false, false));
+    }
+
+    #[test]
+    fn respects_encounter_rules() {
+        use crate::reminder::rules::RuleKind;
+
+        let tracker = tracker_with_self(BuffState::None, BuffState::None);
+        let mut reminder = Reminder::new();
+        reminder.settings.rules = vec![
+            EncounterRule::new(BOSS as u32, RuleTarget::Util, RuleKind::Block),
+            EncounterRule::new(BOSS as u32 + 1, RuleTarget::Food, RuleKind::Allow),
+        ];
+        reminder.start_encounter(BOSS, START);
+
+        reminder.update_pending_check(&tracker, START + CHECK_TIMEOUT);
+        assert_eq!(triggered(&reminder), (false, false));
+
+        let mut reminder = Reminder::new();
+        reminder.settings.rules = vec![EncounterRule::new(
+            BOSS as u32,
+            RuleTarget::Food,
+            RuleKind::Allow,
+        )];
+        reminder.start_encounter(BOSS, START);
+
+        reminder.update_pending_check(&tracker, START + CHECK_TIMEOUT);
+        assert_eq!(triggered(&reminder), (true, true));
+    }
+
+    #[test]
+    fn only_checks_once() {
+        let tracker = tracker_with_self(BuffState::None, BuffState::None);
+        let mut reminder = Reminder::new();
+        reminder.start_encounter(BOSS, START);
+
+        reminder.update_pending_check(&tracker, START + CHECK_TIMEOUT);
+        assert_eq!(triggered(&reminder), (true, true));
+
+        reminder.food_trigger = None;
+        reminder.util_trigger = None;
+        reminder.self_buff_initial(START + CHECK_TIMEOUT);
+        reminder.update_pending_check(&tracker, START + 2 * CHECK_TIMEOUT);
+        assert_eq!(triggered(&reminder), (false, false));
+    }
+
+    #[test]
+    fn squad_threshold() {
+        let mut tracker = tracker_with_self(BuffState::None, BuffState::None);
+        add_player(&mut tracker, 1, 1, BuffState::None, BuffState::Some(1234));
+        add_player(&mut tracker, 2, 1, BuffState::Some(1234), BuffState::None);
+        add_player(
+            &mut tracker,
+            3,
+            1,
+            BuffState::Some(1234),
+            BuffState::Some(5678),
+        );
+
+        let mut reminder = squad_reminder();
+        reminder.settings.squad_threshold = 3;
+        reminder.check_squad(&tracker);
+        assert!(reminder.squad_trigger.is_none());
+
+        reminder.settings.squad_threshold = 2;
+        reminder.check_squad(&tracker);
+        assert!(reminder.squad_trigger.is_some());
+        assert_eq!(reminder.squad_missing, ["char1 (Food)", "char2 (Utility)"]);
+    }
+
+    #[test]
+    fn squad_scope() {
+        let mut tracker = tracker_with_self(BuffState::Some(1234), BuffState::Some(5678));
+        add_player(&mut tracker, 1, 1, BuffState::None, BuffState::None);
+        add_player(&mut tracker, 2, 2, BuffState::None, BuffState::None);
+
+        let mut reminder = squad_reminder();
+        reminder.settings.squad_scope = SquadScope::Subgroup;
+        reminder.check_squad(&tracker);
+        assert_eq!(reminder.squad_missing, ["char1 (Food & Utility)"]);
+
+        let mut reminder = squad_reminder();
+        reminder.settings.squad_scope = SquadScope::Squad;
+        reminder.check_squad(&tracker);
+        assert_eq!(
+            reminder.squad_missing,
+            ["char1 (Food & Utility)", "char2 (Food & Utility)"]
+        );
+    }
+
+    #[test]
+    fn squad_unknown_states() {
+        let mut tracker = tracker_with_self(BuffState::None, BuffState::None);
+        add_player(&mut tracker, 1, 1, BuffState::Unknown, BuffState::Unknown);
+        add_player(
+            &mut tracker,
+            2,
+            1,
+            BuffState::Unknown,
+            BuffState::Some(DIMINISHED),
+        );
+        add_player(
+            &mut tracker,
+            3,
+            1,
+            BuffState::Some(MALNOURISHED),
+            BuffState::Unknown,
+        );
+
+        let mut reminder = squad_reminder();
+        reminder.check_squad(&tracker);
+        assert_eq!(reminder.squad_missing, ["char2 (Utility)", "char3 (Food)"]);
+    }
+
+    #[test]
+    fn squad_disabled() {
+        let mut tracker = tracker_with_self(BuffState::None, BuffState::None);
+        add_player(&mut tracker, 1, 1, BuffState::None, BuffState::None);
+
+        let mut reminder = squad_reminder();
+        reminder.settings.squad_alert = false;
+        reminder.check_squad(&tracker);
+        assert!(reminder.squad_trigger.is_none());
+    }
+
+    #[test]
+    fn chat_command_cooldown() {
+        let tracker = tracker_with_self(BuffState::None, BuffState::None);
+        let mut reminder = Reminder::new();
+
+        assert!(reminder.chat_command(&tracker));
+        assert_eq!(triggered(&reminder), (true, true));
+
+        reminder.food_trigger = None;
+        reminder.util_trigger = None;
+        assert!(!reminder.chat_command(&tracker));
+        assert_eq!(triggered(&reminder), (false, false));
+    }
+}
@@ -1,5 +1,6 @@
 pub mod custom;
 pub mod event;
+pub mod rules;
 pub mod settings;
 pub mod ui;
 
@@ -21,6 +22,9 @@ pub struct Reminder {
     /// Current reminder settings.
     pub settings: ReminderSettings,
 
+    /// Buffs expected on the current character.
+    pub expected: ExpectedBuffs,
+
     /// Timestamp of food reminder trigger.
     food_trigger: Option<Instant>,
 
@@ -30,11 +34,29 @@ pub struct Reminder {
     /// Timestamps of custom buff reminder triggers.
     custom_triggers: BTreeMap<u32, Instant>,
 
+    /// Timestamp of squad alert trigger.
+    squad_trigger: Option<Instant>,
+
+    /// Squad members missing food or utility for the squad alert.
+    squad_missing: Vec<String>,
+
     /// Current ongoing encounter.
     encounter: Option<Encounter>,
 
+    /// Map id from the last MumbleLink update.
+    map_id: u32,
+
+    /// Timestamp of a pending check after entering an instance.
+    map_check: Option<Instant>,
+
+    /// Timestamp of the last accepted chat command.
+    chat_command_time: Option<Instant>,
+
     /// Reset confirm state for custom reminders.
     custom_reset: bool,
+
+    /// Whether the reminder is currently being placed.
+    placing: bool,
 }
 
 impl Reminder {
@@ -52,11 +74,18 @@ impl Reminder {
                 }
             },
             settings: ReminderSettings::new(),
+            expected: ExpectedBuffs::default(),
             food_trigger: None,
             util_trigger: None,
             custom_triggers: BTreeMap::new(),
+            squad_trigger: None,
+            squad_missing: Vec::new(),
             encounter: None,
+            map_id: 0,
+            map_check: None,
+            chat_command_time: None,
             custom_reset: false,
+            placing: false,
         }
     }
 
@@ -73,6 +102,16 @@ impl Reminder {
         &self.settings.custom
     }
 
+    /// Whether the reminder is currently being placed.
+    pub fn is_placing(&self) -> bool {
+        self.placing
+    }
+
+    /// Toggles the placing mode of the reminder.
+    pub fn toggle_placing(&mut self) {
+        self.placing = !self.placing;
+    }
+
     /// Triggers all reminders.
     pub fn trigger_all(&mut self) {
         self.trigger_food();
@@ -103,9 +142,23 @@ impl Reminder {
         }
     }
 
+    /// Triggers the squad alert for the given squad members.
+    pub fn trigger_squad(&mut self, missing: Vec<String>) {
+        info!("Squad alert triggered for {} players", missing.len());
+        self.squad_trigger = Some(Instant::now());
+        self.squad_missing = missing;
+    }
+
+    /// Checks whether the custom reminder is required on the current character.
+    ///
+    /// Inactive custom reminders are required when listed by the current build.
+    pub fn is_required(&self, remind: &CustomReminder) -> bool {
+        remind.active || self.expected.custom.contains(&remind.id)
+    }
+
     /// Triggers the custom buff reminder.
     pub fn trigger_custom(&mut self, id: u32) {
-        if let Some(remind @ CustomReminder { active: true, .. }) = self.custom(id) {
+        if let Some(remind) = self.custom(id).filter(|remind| self.is_required(remind)) {
             let applies = if let Some(mumble) = &self.mumble {
                 let link = mumble.read();
                 remind.mode.is_map(link.context.map_id)
@@ -128,6 +181,21 @@ impl Default for Reminder {
     }
 }
 
+/// Buffs expected on the current character.
+///
+/// Used to display the missing item in the reminder.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ExpectedBuffs {
+    /// Expected food buff id.
+    pub food: Option<u32>,
+
+    /// Expected utility buff id.
+    pub util: Option<u32>,
+
+    /// Additionally required custom buff ids.
+    pub custom: Vec<u32>,
+}
+
 #[derive(Debug)]
 struct Encounter {
     /// Id of the encounter target.
@@ -138,4 +206,7 @@ struct Encounter {
 
     /// Whether there is a pending check for the encounter.
     pub pending_check: bool,
+
+    /// Time of the last buff initial event on self.
+    pub last_initial: Option<u64>,
 }
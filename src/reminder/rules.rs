@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+use strum::{AsRefStr, EnumIter};
+
+/// Rule restricting reminders for a boss encounter.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncounterRule {
+    /// Species id of the boss.
+    pub species: u32,
+
+    /// Reminder type the rule applies to.
+    #[serde(default)]
+    pub target: RuleTarget,
+
+    /// Whether the rule allows or blocks the reminder.
+    #[serde(default)]
+    pub kind: RuleKind,
+}
+
+impl EncounterRule {
+    /// Creates a new encounter rule.
+    pub const fn new(species: u32, target: RuleTarget, kind: RuleKind) -> Self {
+        Self {
+            species,
+            target,
+            kind,
+        }
+    }
+
+    /// Creates a new empty encounter rule.
+    pub const fn empty() -> Self {
+        Self::new(0, RuleTarget::All, RuleKind::Block)
+    }
+
+    /// Checks whether the rule applies to the given reminder type.
+    pub fn applies_to(&self, target: RuleTarget) -> bool {
+        self.target == RuleTarget::All || self.target == target
+    }
+
+    /// Checks whether a reminder type is allowed for the encounter with the given species id.
+    ///
+    /// Blocked species are never allowed.
+    /// If there are allowed species for the reminder type, only those are allowed.
+    pub fn is_allowed(rules: &[Self], species: u64, target: RuleTarget) -> bool {
+        let mut has_allow = false;
+        let mut allowed = false;
+
+        for rule in rules.iter().filter(|rule| rule.applies_to(target)) {
+            let matches = u64::from(rule.species) == species;
+            match rule.kind {
+                RuleKind::Block if matches => return false,
+                RuleKind::Block => {}
+                RuleKind::Allow => {
+                    has_allow = true;
+                    allowed |= matches;
+                }
+            }
+        }
+
+        !has_allow || allowed
+    }
+}
+
+/// Reminder type an [`EncounterRule`] applies to.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    AsRefStr,
+    EnumIter,
+    Serialize,
+    Deserialize,
+)]
+pub enum RuleTarget {
+    #[default]
+    All,
+    Food,
+    #[strum(serialize = "Utility")]
+    Util,
+    Custom,
+}
+
+/// Kind of an [`EncounterRule`].
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    AsRefStr,
+    EnumIter,
+    Serialize,
+    Deserialize,
+)]
+pub enum RuleKind {
+    Allow,
+    #[default]
+    Block,
+}
@@ -1,7 +1,9 @@
-use super::{custom::CustomReminder, Reminder};
+use super::{custom::CustomReminder, rules::EncounterRule, Reminder};
 use arc_util::settings::HasSettings;
+use arcdps::extras::UserRole;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
+use strum::{AsRefStr, EnumIter};
 
 // TODO: game mode setting for inbuilt food & util reminders
 
@@ -21,11 +23,26 @@ pub struct ReminderSettings {
     pub duration: Duration,
 
     /// Position of the reminder display.
-    pub position: f32,
+    pub position: Position,
+
+    /// Whether to use separate positions for food, utility & custom reminders.
+    pub separate_positions: bool,
+
+    /// Position of the food reminder display.
+    pub food_position: Position,
+
+    /// Position of the utility reminder display.
+    pub util_position: Position,
+
+    /// Position of the custom reminder display.
+    pub custom_position: Position,
 
     /// Whether to remind only for boss encounters.
     pub only_bosses: bool,
 
+    /// Rules for reminders in boss encounters.
+    pub rules: Vec<EncounterRule>,
+
     /// Whether to remind on encounter start.
     pub encounter_start: bool,
 
@@ -35,8 +52,32 @@ pub struct ReminderSettings {
     /// Whether to remind during an encounter.
     pub during_encounter: bool,
 
+    /// Whether to remind when entering an instance.
+    pub instance_enter: bool,
+
+    /// Whether to remind when a ready check is started.
+    pub ready_check: bool,
+
+    /// Whether to remind when the chat command is posted in squad or party chat.
+    pub chat_command: bool,
+
+    /// Keyword of the chat command.
+    pub chat_keyword: String,
+
+    /// Minimum squad role required to use the chat command.
+    pub chat_role: ChatRole,
+
     /// Whether to always remind when becoming malnourished/diminished.
     pub always_mal_dim: bool,
+
+    /// Whether to alert for squad members missing food or utility on encounter start.
+    pub squad_alert: bool,
+
+    /// Minimum amount of players missing food or utility to trigger the squad alert.
+    pub squad_threshold: usize,
+
+    /// Players considered for the squad alert.
+    pub squad_scope: SquadScope,
 }
 
 impl ReminderSettings {
@@ -47,12 +88,25 @@ impl ReminderSettings {
             util: true,
             custom: CustomReminder::defaults(),
             duration: Reminder::DEFAULT_DURATION,
-            position: 0.2,
+            position: Position::new(0.5, 0.2),
+            separate_positions: false,
+            food_position: Position::new(0.5, 0.2),
+            util_position: Position::new(0.5, 0.25),
+            custom_position: Position::new(0.5, 0.3),
             only_bosses: true,
+            rules: Vec::new(),
             encounter_start: true,
             encounter_end: true,
             during_encounter: true,
+            instance_enter: true,
+            ready_check: true,
+            chat_command: false,
+            chat_keyword: "!food".into(),
+            chat_role: ChatRole::Lieutenant,
             always_mal_dim: true,
+            squad_alert: false,
+            squad_threshold: 1,
+            squad_scope: SquadScope::Squad,
         }
     }
 }
@@ -63,6 +117,122 @@ impl Default for ReminderSettings {
     }
 }
 
+/// Minimum squad role for the chat command.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    AsRefStr,
+    EnumIter,
+    Serialize,
+    Deserialize,
+)]
+pub enum ChatRole {
+    /// Only the squad leader.
+    #[strum(serialize = "Squad Leader")]
+    SquadLeader,
+
+    /// Squad leader & lieutenants.
+    #[default]
+    Lieutenant,
+
+    /// All squad members.
+    Member,
+}
+
+impl ChatRole {
+    /// Checks whether the given [`UserRole`] is allowed.
+    pub fn allows(&self, role: UserRole) -> bool {
+        match role {
+            UserRole::SquadLeader => true,
+            UserRole::Lieutenant => *self >= Self::Lieutenant,
+            UserRole::Member => *self >= Self::Member,
+            _ => false,
+        }
+    }
+}
+
+/// Players considered for the squad alert.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    AsRefStr,
+    EnumIter,
+    Serialize,
+    Deserialize,
+)]
+pub enum SquadScope {
+    /// Only players in the own subgroup.
+    Subgroup,
+
+    /// All players in the squad.
+    #[default]
+    Squad,
+}
+
+/// Position of a reminder display.
+///
+/// Coordinates are relative to the screen size and anchor the center of the display.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(from = "PositionData")]
+pub struct Position {
+    /// Horizontal position.
+    pub x: f32,
+
+    /// Vertical position.
+    pub y: f32,
+}
+
+impl Position {
+    /// Creates a new position.
+    pub const fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+
+    /// Converts the position to screen coordinates.
+    pub fn to_screen(self, [width, height]: [f32; 2]) -> [f32; 2] {
+        [self.x * width, self.y * height]
+    }
+
+    /// Creates a position from screen coordinates.
+    pub fn from_screen([x, y]: [f32; 2], [width, height]: [f32; 2]) -> Self {
+        Self::new((x / width).clamp(0.0, 1.0), (y / height).clamp(0.0, 1.0))
+    }
+}
+
+/// Position data for backwards compatibility.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(untagged)]
+enum PositionData {
+    /// Old vertical position with horizontally centered display.
+    Vertical(f32),
+
+    /// Free position.
+    Free { x: f32, y: f32 },
+}
+
+impl From<PositionData> for Position {
+    fn from(data: PositionData) -> Self {
+        match data {
+            PositionData::Vertical(y) => Self::new(0.5, y),
+            PositionData::Free { x, y } => Self::new(x, y),
+        }
+    }
+}
+
 impl HasSettings for Reminder {
     type Settings = ReminderSettings;
 
@@ -1,20 +1,34 @@
-use super::{custom::CustomReminder, Reminder};
+use super::{custom::CustomReminder, settings::Position, Reminder};
+use crate::{
+    assets::{FOOD_ICON, UNKNOWN_ICON, UTIL_ICON},
+    data::{DefinitionKind, Definitions},
+};
 use arc_util::{
     colors::RED,
-    ui::{render, Component},
+    ui::{render, render::Icon, Component},
 };
 use arcdps::{
     exports::{self, CoreColor},
-    imgui::{self, Ui},
+    imgui::{self, Image, TextureId, Ui},
 };
 use imgui::Condition;
 use std::time::{Duration, Instant};
+use windows::core::Interface;
 
 // TODO: split component with custom text and add to arc_util
 
 /// Font size used by the reminder.
 const FONT_SIZE: f32 = 2.0;
 
+/// Spacing between icon and text.
+const ICON_SPACING: f32 = 5.0;
+
+/// Maximum amount of squad members listed per line in the squad alert.
+const SQUAD_PER_LINE: usize = 3;
+
+/// Maximum amount of squad members listed in the squad alert.
+const SQUAD_MAX_LISTED: usize = 9;
+
 impl Reminder {
     /// Checks if a trigger is currently active and resets it if necessary.
     fn update_trigger(trigger: &mut Option<Instant>, duration: Duration) -> bool {
@@ -33,49 +47,132 @@ impl Reminder {
         Instant::now().saturating_duration_since(time) <= duration
     }
 
-    /// Helper to render text.
-    fn render_text(ui: &Ui, text: &str) {
+    /// Helper to render a line of text with an optional icon.
+    fn render_text(ui: &Ui, icon: Option<&Icon>, text: &str) {
         // grab colors
         let colors = exports::colors();
         let red = colors.core(CoreColor::LightRed).unwrap_or(RED);
 
-        // adjust cursor to center text
+        // adjust cursor to center icon & text
         let [cursor_x, cursor_y] = ui.cursor_pos();
-        let [text_width, _] = ui.calc_text_size(text);
+        let [text_width, text_height] = ui.calc_text_size(text);
+        let icon_width = if icon.is_some() {
+            text_height + ICON_SPACING
+        } else {
+            0.0
+        };
         let window_width = ui.window_content_region_width();
-        ui.set_cursor_pos([cursor_x + 0.5 * (window_width - text_width), cursor_y]);
+        ui.set_cursor_pos([
+            cursor_x + 0.5 * (window_width - icon_width - text_width),
+            cursor_y,
+        ]);
+
+        // render icon
+        if let Some(icon) = icon {
+            Image::new(TextureId::from(icon.as_raw()), [text_height, text_height]).build(ui);
+            ui.same_line_with_spacing(0.0, ICON_SPACING);
+        }
 
         // render text
         ui.text_colored(red, text);
     }
 
-    /// Renders the custom reminder reset button.
-    pub fn render_custom_reset(&mut self, ui: &Ui) {
-        if render::reset_button(ui, "Reset custom", &mut self.custom_reset) {
-            self.settings.custom = CustomReminder::defaults();
+    /// Returns the reminder text for a buff kind with an optional expected item.
+    fn reminder_text(kind: &str, item: Option<&str>) -> String {
+        match item {
+            Some(name) => format!("{kind} reminder: {name}"),
+            None => format!("{kind} reminder!"),
         }
     }
-}
 
-impl Component<()> for Reminder {
-    fn render(&mut self, ui: &Ui, _: ()) {
-        // update triggers
-        let food = Self::update_trigger(&mut self.food_trigger, self.settings.duration);
-        let util = Self::update_trigger(&mut self.util_trigger, self.settings.duration);
-        self.custom_triggers
-            .retain(|_, time| Self::is_triggered(*time, self.settings.duration));
+    /// Renders the food reminder text.
+    fn render_food(ui: &Ui, defs: &Definitions, expected: Option<u32>) {
+        let item = expected.and_then(|id| match defs.definition(id) {
+            Some(DefinitionKind::Food(data)) => Some(data.name.as_str()),
+            _ => None,
+        });
+        Self::render_text(ui, FOOD_ICON.as_ref(), &Self::reminder_text("Food", item));
+    }
+
+    /// Renders the utility reminder text.
+    fn render_util(ui: &Ui, defs: &Definitions, expected: Option<u32>) {
+        let item = expected.and_then(|id| match defs.definition(id) {
+            Some(DefinitionKind::Util(data)) => Some(data.name.as_str()),
+            _ => None,
+        });
+        Self::render_text(
+            ui,
+            UTIL_ICON.as_ref(),
+            &Self::reminder_text("Utility", item),
+        );
+    }
 
-        // check if any is triggered
-        if food || util || !self.custom_triggers.is_empty() {
-            // calculate window position
-            let [screen_width, screen_height] = ui.io().display_size;
+    /// Renders the custom reminder texts.
+    fn render_custom(ui: &Ui, all: &[CustomReminder], ids: &[u32]) {
+        for id in ids {
+            if let Some(remind) = all.iter().find(|remind| remind.id == *id) {
+                Self::render_text(
+                    ui,
+                    UNKNOWN_ICON.as_ref(),
+                    &Self::reminder_text("Buff", Some(remind.display_name())),
+                );
+            }
+        }
+    }
 
+    /// Renders the squad alert texts.
+    ///
+    /// Names are split into multiple lines and capped to keep the alert on screen.
+    fn render_squad(ui: &Ui, missing: &[String]) {
+        Self::render_text(ui, None, "Squad missing buffs:");
+        let listed = &missing[..missing.len().min(SQUAD_MAX_LISTED)];
+        for line in listed.chunks(SQUAD_PER_LINE) {
+            Self::render_text(ui, None, &line.join(", "));
+        }
+        if missing.len() > listed.len() {
+            let more = missing.len() - listed.len();
+            Self::render_text(ui, None, &format!("+{more} more"));
+        }
+    }
+
+    /// Renders a reminder window at the given position.
+    ///
+    /// While placing, the window can be dragged and the new position is saved.
+    fn render_window(
+        ui: &Ui,
+        id: &str,
+        position: &mut Position,
+        placing: bool,
+        contents: impl FnOnce(),
+    ) {
+        let screen_size = ui.io().display_size;
+        let [screen_width, _] = screen_size;
+
+        if placing {
+            // render movable window with background
+            let name = format!("##food-reminder-place-{id}");
+            imgui::Window::new(&name)
+                .position(position.to_screen(screen_size), Condition::Appearing)
+                .position_pivot([0.5, 0.5])
+                .always_auto_resize(true)
+                .no_decoration()
+                .movable(true)
+                .focus_on_appearing(false)
+                .build(ui, || {
+                    ui.set_window_font_scale(FONT_SIZE);
+                    contents();
+
+                    // save center of window as new position
+                    let [x, y] = ui.window_pos();
+                    let [width, height] = ui.window_size();
+                    *position =
+                        Position::from_screen([x + 0.5 * width, y + 0.5 * height], screen_size);
+                });
+        } else {
             // render "invisible" window with text
-            imgui::Window::new("##food-reminder-reminder")
-                .position(
-                    [0.5 * screen_width, self.settings.position * screen_height],
-                    Condition::Always,
-                )
+            let name = format!("##food-reminder-{id}");
+            imgui::Window::new(&name)
+                .position(position.to_screen(screen_size), Condition::Always)
                 .position_pivot([0.5, 0.5])
                 .content_size([screen_width, 0.0])
                 .always_auto_resize(true)
@@ -86,22 +183,81 @@ impl Component<()> for Reminder {
                 .focus_on_appearing(false)
                 .build(ui, || {
                     ui.set_window_font_scale(FONT_SIZE);
+                    contents();
+                });
+        }
+    }
 
-                    // food/util
-                    match (food, util) {
-                        (true, true) => Self::render_text(ui, "Food & Utility reminder!"),
-                        (true, false) => Self::render_text(ui, "Food reminder!"),
-                        (false, true) => Self::render_text(ui, "Utility reminder!"),
-                        (false, false) => {}
-                    }
+    /// Renders the custom reminder reset button.
+    pub fn render_custom_reset(&mut self, ui: &Ui) {
+        if render::reset_button(ui, "Reset custom", &mut self.custom_reset) {
+            self.settings.custom = CustomReminder::defaults();
+        }
+    }
+}
+
+impl Component<&Definitions> for Reminder {
+    fn render(&mut self, ui: &Ui, defs: &Definitions) {
+        // update triggers
+        let food = Self::update_trigger(&mut self.food_trigger, self.settings.duration);
+        let util = Self::update_trigger(&mut self.util_trigger, self.settings.duration);
+        let squad = Self::update_trigger(&mut self.squad_trigger, self.settings.duration);
+        self.custom_triggers
+            .retain(|_, time| Self::is_triggered(*time, self.settings.duration));
 
-                    // custom reminders
-                    for id in self.custom_triggers.keys() {
-                        if let Some(remind) = self.custom(*id) {
-                            Self::render_text(ui, &format!("{} reminder!", remind.display_name()));
-                        }
+        // show everything as preview while placing
+        let placing = self.placing;
+        let food = food || placing;
+        let util = util || placing;
+        let custom_ids = if placing {
+            self.settings
+                .custom
+                .iter()
+                .filter(|remind| remind.active)
+                .map(|remind| remind.id)
+                .collect()
+        } else {
+            self.custom_triggers.keys().copied().collect::<Vec<_>>()
+        };
+        let custom = !custom_ids.is_empty() || squad;
+
+        let expected = &self.expected;
+        let squad_missing = &self.squad_missing;
+        let settings = &mut self.settings;
+        let all_custom = &settings.custom;
+
+        if settings.separate_positions {
+            if food {
+                Self::render_window(ui, "food", &mut settings.food_position, placing, || {
+                    Self::render_food(ui, defs, expected.food)
+                });
+            }
+            if util {
+                Self::render_window(ui, "util", &mut settings.util_position, placing, || {
+                    Self::render_util(ui, defs, expected.util)
+                });
+            }
+            if custom {
+                Self::render_window(ui, "custom", &mut settings.custom_position, placing, || {
+                    Self::render_custom(ui, all_custom, &custom_ids);
+                    if squad {
+                        Self::render_squad(ui, squad_missing);
                     }
                 });
+            }
+        } else if food || util || custom {
+            Self::render_window(ui, "reminder", &mut settings.position, placing, || {
+                if food {
+                    Self::render_food(ui, defs, expected.food);
+                }
+                if util {
+                    Self::render_util(ui, defs, expected.util);
+                }
+                Self::render_custom(ui, all_custom, &custom_ids);
+                if squad {
+                    Self::render_squad(ui, squad_missing);
+                }
+            });
         }
     }
 }
@@ -0,0 +1,185 @@
+use super::{buff::BuffState, Tracker};
+use crate::data::{DIMINISHED, MALNOURISHED};
+use arc_util::tracking::Player;
+
+/// Maximum amount of characters in a Guild Wars 2 chat message.
+pub const CHAT_LIMIT: usize = 199;
+
+/// Marker for names cut to fit into a message.
+const ELLIPSIS: char = '…';
+
+/// Chat callout split into chunks to be copied one at a time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Callout {
+    /// Message chunks fitting into the chat.
+    pub chunks: Vec<String>,
+
+    /// Index of the next chunk to copy.
+    pub next: usize,
+}
+
+impl Callout {
+    /// Creates a new callout from message chunks.
+    pub const fn new(chunks: Vec<String>) -> Self {
+        Self { chunks, next: 0 }
+    }
+
+    /// Returns the next chunk and advances.
+    pub fn advance(&mut self) -> Option<&str> {
+        let chunk = self.chunks.get(self.next)?;
+        self.next += 1;
+        Some(chunk)
+    }
+
+    /// Checks whether all chunks have been copied.
+    pub fn is_done(&self) -> bool {
+        self.next >= self.chunks.len()
+    }
+}
+
+impl Tracker {
+    /// Creates a callout for players missing food or utility.
+    ///
+    /// Only players tracked by arcdps are included, squad members out of range are not.
+    /// Players with unknown buff states are not included either.
+    pub fn create_callout(&self) -> Callout {
+        let name = |player: &Player| {
+            if self.settings.callout_accounts {
+                player.account.clone()
+            } else {
+                player.character.clone()
+            }
+        };
+        let missing = |state: BuffState<u32>, negative: u32| match state {
+            BuffState::None => true,
+            BuffState::Some(id) => id == negative,
+            BuffState::Unknown => false,
+        };
+
+        let food = self
+            .players
+            .iter()
+            .filter(|entry| missing(entry.data.food.state, MALNOURISHED))
+            .map(|entry| name(&entry.player))
+            .collect::<Vec<_>>();
+        let util = self
+            .players
+            .iter()
+            .filter(|entry| missing(entry.data.util.state, DIMINISHED))
+            .map(|entry| name(&entry.player))
+            .collect::<Vec<_>>();
+
+        Callout::new(chunk_message(
+            &[("Missing food", food), ("Missing util", util)],
+            CHAT_LIMIT,
+        ))
+    }
+}
+
+/// Splits labeled sections of names into messages not exceeding the character limit.
+///
+/// Sections are joined with `|` and continued sections repeat their label.
+/// Names not fitting into a message on their own are cut at a character boundary and marked with an ellipsis.
+pub fn chunk_message(sections: &[(&str, Vec<String>)], limit: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+
+    for (label, names) in sections {
+        let mut in_section = false;
+        for name in names {
+            loop {
+                let part = if in_section {
+                    format!(", {name}")
+                } else if current.is_empty() {
+                    format!("{label}: {name}")
+                } else {
+                    format!(" | {label}: {name}")
+                };
+
+                if current.chars().count() + part.chars().count() <= limit {
+                    current.push_str(&part);
+                    in_section = true;
+                    break;
+                } else if current.is_empty() {
+                    current = part.chars().take(limit.saturating_sub(1)).collect();
+                    current.push(ELLIPSIS);
+                    in_section = true;
+                    break;
+                } else {
+                    chunks.push(std::mem::take(&mut current));
+                    in_section = false;
+                }
+            }
+        }
+    }
+
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn single() {
+        let food = names(&["Alice", "Bob"]);
+        let util = names(&["Carol"]);
+        assert_eq!(
+            chunk_message(
+                &[("Missing food", food), ("Missing util", util.clone())],
+                199
+            ),
+            ["Missing food: Alice, Bob | Missing util: Carol"]
+        );
+        assert!(
+            chunk_message(&[("Missing food", vec![]), ("Missing util", vec![])], 199).is_empty()
+        );
+        assert_eq!(
+            chunk_message(&[("Missing food", vec![]), ("Missing util", util)], 199),
+            ["Missing util: Carol"]
+        );
+    }
+
+    #[test]
+    fn split() {
+        let food = names(&["Alice", "Bob", "Carol"]);
+        let util = names(&["Dave"]);
+        let chunks = chunk_message(&[("Food", food), ("Util", util)], 20);
+        assert_eq!(chunks, ["Food: Alice, Bob", "Food: Carol", "Util: Dave"]);
+        assert!(chunks.iter().all(|chunk| chunk.chars().count() <= 20));
+    }
+
+    #[test]
+    fn truncate() {
+        let food = names(&["Bob", "Averyveryverylongname", "Carol"]);
+        let chunks = chunk_message(&[("Food", food)], 12);
+        assert_eq!(chunks, ["Food: Bob", "Food: Avery…", "Food: Carol"]);
+        assert!(chunks.iter().all(|chunk| chunk.chars().count() <= 12));
+    }
+
+    #[test]
+    fn full_squad() {
+        let food = (0..50)
+            .map(|i| format!("Character Name {i}"))
+            .collect::<Vec<_>>();
+        let chunks = chunk_message(&[("Missing food", food)], CHAT_LIMIT);
+        assert!(chunks.len() > 1);
+        assert!(chunks.iter().all(
+            |chunk| chunk.chars().count() <= CHAT_LIMIT && chunk.starts_with("Missing food: ")
+        ));
+        assert_eq!(
+            chunks
+                .iter()
+                .map(|chunk| chunk.matches("Character").count())
+                .sum::<usize>(),
+            50
+        );
+    }
+}
@@ -0,0 +1,253 @@
+use super::{
+    buff::{BuffState, Buffs},
+    settings::ExportFormat,
+    squad::SquadMember,
+};
+use crate::{
+    data::{spec_name, Definitions, NO_BUFF_TEXT, UNKNOWN_BUFF_TEXT, UNKNOWN_STATE_TEXT},
+    reminder::custom::CustomReminder,
+};
+use arc_util::tracking::Player;
+use arcdps::Profession;
+use serde::Serialize;
+
+/// Row of an exported squad table.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ExportRow {
+    pub subgroup: usize,
+    pub account: String,
+    pub character: String,
+    pub profession: String,
+    pub food: String,
+    pub util: String,
+
+    /// Custom buff states in reminder order.
+    pub custom: Vec<CustomState>,
+}
+
+/// State of a custom buff in an exported row.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CustomState {
+    pub name: String,
+    pub state: String,
+}
+
+impl ExportRow {
+    /// Creates a new row for a tracked player & buffs.
+    pub fn tracked(
+        defs: &Definitions,
+        custom: &[CustomReminder],
+        player: &Player,
+        buffs: &Buffs,
+    ) -> Self {
+        Self {
+            subgroup: player.subgroup,
+            account: player.account.clone(),
+            character: player.character.clone(),
+            profession: match player.profession {
+                Profession::Unknown => String::new(),
+                prof => spec_name(prof, player.elite).into(),
+            },
+            food: Self::buff_text(defs, buffs.food.state),
+            util: Self::buff_text(defs, buffs.util.state),
+            custom: Self::custom_states(custom, |id| buffs.custom_state(id)),
+        }
+    }
+
+    /// Creates a new row for a squad member not tracked by arcdps.
+    pub fn untracked(custom: &[CustomReminder], account: &str, member: &SquadMember) -> Self {
+        Self {
+            subgroup: member.subgroup,
+            account: account.into(),
+            character: String::new(),
+            profession: String::new(),
+            food: UNKNOWN_STATE_TEXT.into(),
+            util: UNKNOWN_STATE_TEXT.into(),
+            custom: Self::custom_states(custom, |_| BuffState::Unknown),
+        }
+    }
+
+    /// Returns the display text for a food or utility state.
+    fn buff_text(defs: &Definitions, state: BuffState<u32>) -> String {
+        match state {
+            BuffState::Unknown => UNKNOWN_STATE_TEXT.into(),
+            BuffState::None => NO_BUFF_TEXT.into(),
+            BuffState::Some(id) => defs
+                .definition(id)
+                .and_then(|def| def.data())
+                .map(|data| data.display.clone())
+                .unwrap_or_else(|| UNKNOWN_BUFF_TEXT.into()),
+        }
+    }
+
+    /// Returns the custom buff states in reminder order.
+    fn custom_states(
+        custom: &[CustomReminder],
+        state: impl Fn(u32) -> BuffState<()>,
+    ) -> Vec<CustomState> {
+        custom
+            .iter()
+            .map(|remind| CustomState {
+                name: remind.display_name().into(),
+                state: match state(remind.id) {
+                    BuffState::Unknown => UNKNOWN_STATE_TEXT,
+                    BuffState::None => "No",
+                    BuffState::Some(_) => "Yes",
+                }
+                .into(),
+            })
+            .collect()
+    }
+
+    /// Returns the cells of the row in column order.
+    fn cells(&self) -> impl Iterator<Item = String> + '_ {
+        [
+            self.subgroup.to_string(),
+            self.account.clone(),
+            self.character.clone(),
+            self.profession.clone(),
+            self.food.clone(),
+            self.util.clone(),
+        ]
+        .into_iter()
+        .chain(self.custom.iter().map(|custom| custom.state.clone()))
+    }
+}
+
+/// Returns the column headers for the exported rows.
+fn headers(custom: &[CustomReminder]) -> Vec<String> {
+    [
+        "Sub",
+        "Account",
+        "Character",
+        "Profession",
+        "Food",
+        "Utility",
+    ]
+    .into_iter()
+    .map(String::from)
+    .chain(custom.iter().map(|remind| remind.display_name().into()))
+    .collect()
+}
+
+/// Exports rows in the given format.
+///
+/// The rows are expected to contain the states of the given custom reminders.
+pub fn export(custom: &[CustomReminder], rows: &[ExportRow], format: ExportFormat) -> String {
+    match format {
+        ExportFormat::Csv => export_csv(custom, rows),
+        ExportFormat::Markdown => export_markdown(custom, rows),
+        ExportFormat::Json => export_json(rows),
+    }
+}
+
+/// Exports rows as CSV.
+fn export_csv(custom: &[CustomReminder], rows: &[ExportRow]) -> String {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer
+        .write_record(headers(custom))
+        .expect("failed to write headers");
+    for row in rows {
+        writer
+            .write_record(row.cells())
+            .expect("failed to write row");
+    }
+    let data = writer.into_inner().expect("failed to write rows");
+    String::from_utf8(data).expect("squad csv is not utf8")
+}
+
+/// Exports rows as Markdown table.
+fn export_markdown(custom: &[CustomReminder], rows: &[ExportRow]) -> String {
+    let line = |cells: Vec<String>| {
+        let cells = cells
+            .iter()
+            .map(|cell| cell.replace('|', "\\|"))
+            .collect::<Vec<_>>();
+        format!("| {} |\n", cells.join(" | "))
+    };
+
+    let headers = headers(custom);
+    let mut result = line(headers.clone());
+    result.push_str(&line(headers.iter().map(|_| "---".into()).collect()));
+    for row in rows {
+        result.push_str(&line(row.cells().collect()));
+    }
+    result
+}
+
+/// Exports rows as JSON.
+fn export_json(rows: &[ExportRow]) -> String {
+    serde_json::to_string_pretty(rows).expect("failed to serialize squad")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reminder::custom::GameMode;
+    use arcdps::extras::UserRole;
+
+    fn custom() -> Vec<CustomReminder> {
+        vec![
+            CustomReminder::new(2, "Reinforced", GameMode::All),
+            CustomReminder::new(1, "Potion", GameMode::Fractal),
+            CustomReminder::new(3, "Potion", GameMode::Raid),
+        ]
+    }
+
+    fn row(character: &str) -> ExportRow {
+        ExportRow {
+            subgroup: 1,
+            account: "Account.1234".into(),
+            character: character.into(),
+            profession: "Firebrand".into(),
+            food: "Power".into(),
+            util: NO_BUFF_TEXT.into(),
+            custom: ExportRow::custom_states(&custom(), |id| match id {
+                1 => BuffState::None,
+                _ => BuffState::Some(()),
+            }),
+        }
+    }
+
+    #[test]
+    fn formats() {
+        let custom = custom();
+        let rows = [row("First"), row("Pipe | Name")];
+
+        let csv = export(&custom, &rows, ExportFormat::Csv);
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("Sub,Account,Character,Profession,Food,Utility,Reinforced,Potion,Potion")
+        );
+        assert_eq!(
+            lines.next(),
+            Some("1,Account.1234,First,Firebrand,Power,None,Yes,No,Yes")
+        );
+
+        let markdown = export(&custom, &rows, ExportFormat::Markdown);
+        let lines = markdown.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(
+            lines[1],
+            "| --- | --- | --- | --- | --- | --- | --- | --- | --- |"
+        );
+        assert!(lines[3].contains("Pipe \\| Name"));
+
+        let json = export(&custom, &rows, ExportFormat::Json);
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed[1]["character"], "Pipe | Name");
+        assert_eq!(parsed[0]["custom"][0]["name"], "Reinforced");
+        assert_eq!(parsed[0]["custom"][1]["state"], "No");
+    }
+
+    #[test]
+    fn untracked() {
+        let custom = custom();
+        let member = SquadMember::new(UserRole::Member, 3, false);
+        let rows = [ExportRow::untracked(&custom, "Other.5678", &member)];
+
+        let csv = export(&custom, &rows, ExportFormat::Csv);
+        assert_eq!(csv.lines().nth(1), Some("3,Other.5678,,,?,?,?,?,?"));
+    }
+}
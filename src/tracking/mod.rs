@@ -1,13 +1,26 @@
 pub mod buff;
+pub mod callout;
+pub mod export;
 pub mod settings;
+pub mod squad;
 pub mod ui;
 
 use self::buff::{BuffState, Buffs};
-use self::settings::TrackerSettings;
-use crate::builds::Builds;
-use arc_util::tracking::{CachedTracker, Entry, Player};
-use log::{debug, log_enabled, Level};
-use std::cmp::Reverse;
+use self::callout::Callout;
+use self::export::ExportRow;
+use self::settings::{ExportFormat, SettingsEntry, TrackerSettings};
+use self::squad::SquadMember;
+use crate::{
+    builds::{Build, Builds},
+    data::Definitions,
+    reminder::{custom::CustomReminder, ExpectedBuffs},
+};
+use arc_util::{
+    settings::Settings,
+    tracking::{CachedTracker, Entry, Player},
+};
+use log::{debug, log_enabled, warn, Level};
+use std::{cmp::Ordering, collections::BTreeMap, fs, io, path::PathBuf};
 use windows::System::VirtualKey;
 
 /// Player tracker.
@@ -19,6 +32,15 @@ pub struct Tracker {
     /// Currently tracked players.
     pub players: CachedTracker<Buffs>,
 
+    /// Squad members from unofficial extras by account name.
+    pub squad: BTreeMap<String, SquadMember>,
+
+    /// Account name of the own player.
+    pub account: Option<String>,
+
+    /// Saved characters of other accounts.
+    other_chars: Vec<SettingsEntry>,
+
     /// Current sorting.
     sorting: Sorting,
 
@@ -29,7 +51,13 @@ pub struct Tracker {
     chars_reset: bool,
 
     /// User-defined builds.
-    builds: Builds,
+    pub builds: Builds,
+
+    /// Result of the last squad export to a file.
+    export_result: Option<io::Result<PathBuf>>,
+
+    /// Chat callout currently being copied.
+    callout: Option<Callout>,
 }
 
 #[allow(dead_code)]
@@ -42,17 +70,150 @@ impl Tracker {
         Self {
             settings: TrackerSettings::new(),
             players: CachedTracker::for_self(),
+            squad: BTreeMap::new(),
+            account: None,
+            other_chars: Vec::new(),
             sorting: Sorting::Sub,
             reverse: false,
             chars_reset: false,
             builds: Builds::new(),
+            export_result: None,
+            callout: None,
         }
     }
 
+    /// Sets the account name of the own player.
+    ///
+    /// Only characters of the account are kept in the cache for own characters.
+    pub fn set_account(&mut self, account: impl Into<String>) {
+        let account = account.into();
+        debug!("Own account set to {}", account);
+
+        // move characters of other accounts out of the cache
+        let others = self
+            .players
+            .cache_iter()
+            .filter(|(player, _)| player.account != account)
+            .cloned()
+            .collect::<Vec<_>>();
+        for (player, _) in &others {
+            self.players.remove_cache_entry(player.character.clone());
+        }
+        self.other_chars.extend(others.into_iter().map(Into::into));
+
+        // move characters of the account into the cache
+        let (own, others): (Vec<_>, Vec<_>) = self
+            .other_chars
+            .drain(..)
+            .partition(|entry| entry.player.account == account);
+        self.players.cache_multiple(own.into_iter().map(Into::into));
+        self.other_chars = others;
+
+        self.account = Some(account);
+    }
+
+    /// Checks whether the player is the own player.
+    ///
+    /// Falls back to the account name if arcdps has not reported the own player (yet).
+    pub fn is_self(&self, player: &Player) -> bool {
+        player.is_self || self.account.as_deref() == Some(player.account.as_str())
+    }
+
+    /// Returns the entry of the own player.
+    pub fn self_entry(&self) -> Option<&Entry<Buffs>> {
+        self.players.get_self().or_else(|| {
+            self.players
+                .iter()
+                .find(|entry| self.is_self(&entry.player))
+        })
+    }
+
+    /// Returns the build assigned to the own character.
+    pub fn self_build(&self) -> Option<&Build> {
+        self.self_entry().and_then(|entry| {
+            self.builds
+                .assigned(&entry.player.account, &entry.player.character)
+        })
+    }
+
+    /// Returns the buffs expected on the own character by its assigned build.
+    pub fn expected_buffs(&self) -> ExpectedBuffs {
+        self.self_build()
+            .map(|build| ExpectedBuffs {
+                food: build.primary_food(),
+                util: build.primary_util(),
+                custom: build.custom.clone(),
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns squad members from unofficial extras not tracked by arcdps.
+    pub fn untracked_members(&self) -> Vec<(String, SquadMember)> {
+        self.squad
+            .iter()
+            .filter(|(account, _)| {
+                !self
+                    .players
+                    .iter()
+                    .any(|entry| entry.player.account == **account)
+            })
+            .map(|(account, member)| (account.clone(), member.clone()))
+            .collect()
+    }
+
+    /// Returns the rows of the squad table for exporting.
+    pub fn export_rows(&self, defs: &Definitions, custom: &[CustomReminder]) -> Vec<ExportRow> {
+        let untracked = if self.settings.show_untracked {
+            self.untracked_members()
+        } else {
+            Vec::new()
+        };
+
+        self.players
+            .iter()
+            .map(|entry| ExportRow::tracked(defs, custom, &entry.player, &entry.data))
+            .chain(
+                untracked
+                    .iter()
+                    .map(|(account, member)| ExportRow::untracked(custom, account, member)),
+            )
+            .collect()
+    }
+
+    /// Writes an export of the squad table to a file in the config directory.
+    pub fn export_to_file(&mut self, format: ExportFormat, contents: &str) {
+        let result =
+            Settings::config_path(format!("arcdps_food_reminder_squad.{}", format.extension()))
+                .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no config directory"))
+                .and_then(|path| fs::write(&path, contents).map(|_| path));
+
+        match &result {
+            Ok(path) => debug!("Exported squad to \"{}\"", path.display()),
+            Err(err) => warn!("Failed to export squad: {err}"),
+        }
+        self.export_result = Some(result);
+    }
+
+    /// Finds the entry of the own player in the given players mutably.
+    fn find_self_mut<'p>(
+        players: &'p mut CachedTracker<Buffs>,
+        account: Option<&str>,
+    ) -> Option<&'p mut Entry<Buffs>> {
+        players
+            .iter_mut()
+            .find(|entry| entry.player.is_self || account == Some(entry.player.account.as_str()))
+    }
+
     /// Adds a new tracked player.
     pub fn add_player(&mut self, player: Player) {
         let id = player.id;
         debug!("Added {} ({})", player.character, id);
+
+        // use account of own player if not known yet
+        if player.is_self && self.account.is_none() {
+            self.set_account(player.account.clone());
+        }
+
         let cached = self.players.add_player_default(player);
 
         if log_enabled!(Level::Debug) && cached {
@@ -69,34 +230,26 @@ impl Tracker {
 
     /// Removes a tracked player, returning `true` if they were tracked.
     pub fn remove_player(&mut self, id: usize) -> bool {
-        self.players.remove_player(id)
+        // own player only identified by account is not cached automatically
+        let uncached = self
+            .players
+            .player(id)
+            .filter(|entry| !entry.player.is_self && self.is_self(&entry.player))
+            .cloned();
+
+        let removed = self.players.remove_player(id);
+        if let Some(Entry { player, data }) = uncached {
+            self.players.cache_multiple([(player.into(), data)]);
+        }
+        removed
     }
 
     /// Sorts the players in the tracker table.
     fn refresh_sort(&mut self) {
-        match (self.sorting, self.reverse) {
-            (Sorting::Sub, false) => self.players.sort_by_key(|entry| entry.player.subgroup),
-            (Sorting::Sub, true) => self
-                .players
-                .sort_by_key(|entry| Reverse(entry.player.subgroup)),
-
-            (Sorting::Name, false) => self
-                .players
-                .sort_by(|a, b| a.player.character.cmp(&b.player.character)),
-            (Sorting::Name, true) => self
-                .players
-                .sort_by(|a, b| Reverse(&a.player.character).cmp(&Reverse(&b.player.character))),
-
-            (Sorting::Food, false) => self.players.sort_by_key(|entry| entry.data.food.state),
-            (Sorting::Food, true) => self
-                .players
-                .sort_by_key(|entry| Reverse(entry.data.food.state)),
-
-            (Sorting::Util, false) => self.players.sort_by_key(|entry| entry.data.util.state),
-            (Sorting::Util, true) => self
-                .players
-                .sort_by_key(|entry| Reverse(entry.data.util.state)),
-        }
+        let (sorting, reverse) = (self.sorting, self.reverse);
+        self.players.sort_by(|a, b| {
+            sorting.compare(reverse, &RowKey::from_entry(a), &RowKey::from_entry(b))
+        });
     }
 
     /// Refreshes the sort if the current sorting matches.
@@ -115,3 +268,51 @@ pub enum Sorting {
     Food,
     Util,
 }
+
+impl Sorting {
+    /// Compares two table rows with the sorting.
+    pub fn compare(&self, reverse: bool, a: &RowKey, b: &RowKey) -> Ordering {
+        let order = match self {
+            Self::Sub => a.subgroup.cmp(&b.subgroup),
+            Self::Name => a.name.cmp(b.name),
+            Self::Food => a.food.cmp(&b.food),
+            Self::Util => a.util.cmp(&b.util),
+        };
+        if reverse {
+            order.reverse()
+        } else {
+            order
+        }
+    }
+}
+
+/// Values of a table row relevant for sorting.
+#[derive(Debug, Clone, Copy)]
+pub struct RowKey<'a> {
+    pub subgroup: usize,
+    pub name: &'a str,
+    pub food: BuffState<u32>,
+    pub util: BuffState<u32>,
+}
+
+impl<'a> RowKey<'a> {
+    /// Creates the sort key for a tracked player.
+    pub fn from_entry(entry: &'a Entry<Buffs>) -> Self {
+        Self {
+            subgroup: entry.player.subgroup,
+            name: &entry.player.character,
+            food: entry.data.food.state,
+            util: entry.data.util.state,
+        }
+    }
+
+    /// Creates the sort key for a squad member not tracked by arcdps.
+    pub fn untracked(account: &'a str, member: &SquadMember) -> Self {
+        Self {
+            subgroup: member.subgroup,
+            name: account,
+            food: BuffState::Unknown,
+            util: BuffState::Unknown,
+        }
+    }
+}
@@ -27,6 +27,15 @@ pub struct TrackerSettings {
     /// Whether to show the subgroup column.
     pub show_sub: bool,
 
+    /// Whether to show the ready status column.
+    pub show_ready: bool,
+
+    /// Whether to open the tracker when a ready check starts.
+    pub ready_check_open: bool,
+
+    /// Whether to show squad members out of range.
+    pub show_untracked: bool,
+
     /// Amount of entries displayed before scrolling.
     pub max_entries_displayed: usize,
 
@@ -35,6 +44,15 @@ pub struct TrackerSettings {
 
     /// Color for player names.
     pub color_name: Color,
+
+    /// Display of the specialization column.
+    pub spec_display: SpecDisplay,
+
+    /// Format for squad exports.
+    pub export_format: ExportFormat,
+
+    /// Whether to use account names in chat callouts.
+    pub callout_accounts: bool,
 }
 
 impl TrackerSettings {
@@ -44,9 +62,15 @@ impl TrackerSettings {
             hotkey: Some(Tracker::DEFAULT_HOTKEY),
             show_icons: true,
             show_sub: true,
+            show_ready: true,
+            ready_check_open: false,
+            show_untracked: true,
             max_entries_displayed: 10,
             color_sub: Color::Sub,
             color_name: Color::Prof,
+            spec_display: SpecDisplay::Tag,
+            export_format: ExportFormat::Markdown,
+            callout_accounts: false,
         }
     }
 }
@@ -82,7 +106,7 @@ impl Default for TrackerState {
 }
 
 /// Saved Player entry.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SettingsEntry {
     pub player: CachedPlayer,
 
@@ -160,13 +184,13 @@ impl HasSettings for Tracker {
         Self::Settings {
             settings: self.settings.clone(),
             own_chars: if self.settings.save_chars {
-                self.players
-                    .get_self()
+                self.self_entry()
                     .cloned()
                     .map(Into::<(CachedPlayer, Buffs)>::into)
                     .into_iter()
                     .chain(self.players.cache_iter().cloned())
                     .map(Into::into)
+                    .chain(self.other_chars.iter().cloned())
                     .collect()
             } else {
                 Vec::new()
@@ -180,6 +204,11 @@ impl HasSettings for Tracker {
         if self.settings.save_chars {
             self.players
                 .cache_multiple(loaded.own_chars.into_iter().map(Into::into));
+
+            // only keep characters of known account in cache
+            if let Some(account) = self.account.clone() {
+                self.set_account(account);
+            }
         }
 
         self.builds.load_settings(loaded.builds);
@@ -204,3 +233,60 @@ impl AsRef<str> for Color {
         }
     }
 }
+
+/// Display of the elite specialization in the tracker.
+///
+/// Players without elite specialization display their profession instead.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, EnumIter, Serialize, Deserialize,
+)]
+pub enum SpecDisplay {
+    /// Specialization is not displayed.
+    None,
+
+    /// Short specialization tag, e.g. "FB" for Firebrand.
+    Tag,
+
+    /// Full specialization name.
+    Name,
+}
+
+impl AsRef<str> for SpecDisplay {
+    fn as_ref(&self) -> &str {
+        match self {
+            Self::None => "Hidden",
+            Self::Tag => "Short tag",
+            Self::Name => "Name",
+        }
+    }
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, EnumIter, Serialize, Deserialize,
+)]
+pub enum ExportFormat {
+    Csv,
+    Markdown,
+    Json,
+}
+
+impl ExportFormat {
+    /// Returns the file extension for the format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Markdown => "md",
+            Self::Json => "json",
+        }
+    }
+}
+
+impl AsRef<str> for ExportFormat {
+    fn as_ref(&self) -> &str {
+        match self {
+            Self::Csv => "CSV",
+            Self::Markdown => "Markdown",
+            Self::Json => "JSON",
+        }
+    }
+}
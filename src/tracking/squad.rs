@@ -0,0 +1,83 @@
+use arcdps::extras::UserRole;
+
+/// Squad member information from unofficial extras.
+#[derive(Debug, Clone)]
+pub struct SquadMember {
+    /// Role of the member in the squad.
+    pub role: UserRole,
+
+    /// Subgroup of the member.
+    pub subgroup: usize,
+
+    /// Whether the member is marked as ready in a ready check.
+    pub ready: bool,
+}
+
+impl SquadMember {
+    /// Creates a new squad member.
+    pub const fn new(role: UserRole, subgroup: usize, ready: bool) -> Self {
+        Self {
+            role,
+            subgroup,
+            ready,
+        }
+    }
+}
+
+/// Checks whether a squad update of a member starts a ready check.
+///
+/// Only a known squad leader changing from not ready to ready starts a ready check.
+/// The first update after joining the squad or reloading the plugin never does.
+pub fn starts_ready_check(previous: Option<&SquadMember>, role: UserRole, ready: bool) -> bool {
+    matches!(role, UserRole::SquadLeader) && ready && previous.map_or(false, |member| !member.ready)
+}
+
+/// Returns the display name for a [`UserRole`].
+pub fn role_name(role: UserRole) -> &'static str {
+    match role {
+        UserRole::SquadLeader => "Squad Leader",
+        UserRole::Lieutenant => "Lieutenant",
+        UserRole::Member => "Member",
+        UserRole::Invited => "Invited",
+        UserRole::Applied => "Applied",
+        _ => "None",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ready_check() {
+        let not_ready = SquadMember::new(UserRole::SquadLeader, 1, false);
+        let ready = SquadMember::new(UserRole::SquadLeader, 1, true);
+
+        assert!(starts_ready_check(
+            Some(&not_ready),
+            UserRole::SquadLeader,
+            true
+        ));
+        assert!(!starts_ready_check(
+            Some(&ready),
+            UserRole::SquadLeader,
+            true
+        ));
+        assert!(!starts_ready_check(
+            Some(&not_ready),
+            UserRole::SquadLeader,
+            false
+        ));
+        assert!(!starts_ready_check(
+            Some(&not_ready),
+            UserRole::Member,
+            true
+        ));
+    }
+
+    #[test]
+    fn ready_check_join() {
+        // first update after joining a squad with an ongoing ready check
+        assert!(!starts_ready_check(None, UserRole::SquadLeader, true));
+    }
+}
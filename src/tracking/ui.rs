@@ -1,21 +1,24 @@
 use super::{
     buff::Buffs,
-    settings::{Color, TrackerSettings},
-    BuffState, Sorting, Tracker,
+    export,
+    settings::{Color, SpecDisplay, TrackerSettings},
+    squad::{role_name, SquadMember},
+    BuffState, RowKey, Sorting, Tracker,
 };
 use crate::{
     assets::{FOOD_ICON, UNKNOWN_ICON, UTIL_ICON},
     buff_ui,
-    combo_ui::render_enum_combo,
+    builds::{ui::Current, BuildRef, Builds, Compliance},
+    combo_ui::{render_combo, render_enum_combo},
     data::{
-        DefinitionKind, Definitions, DIMINISHED, MALNOURISHED, NO_BUFF_TEXT, UNKNOWN_BUFF_TEXT,
-        UNKNOWN_STATE_TEXT,
+        spec_name, spec_tag, DefinitionKind, Definitions, DIMINISHED, MALNOURISHED, NO_BUFF_TEXT,
+        UNKNOWN_BUFF_TEXT, UNKNOWN_STATE_TEXT,
     },
     reminder::custom::CustomReminder,
 };
 use arc_util::{
-    colors::{GREEN, RED, YELLOW},
-    tracking::Entry,
+    colors::{GREEN, GREY, RED, YELLOW},
+    tracking::{Entry, Player},
     ui::{
         render::{self, TableIconColumn},
         Component, Windowable,
@@ -23,9 +26,11 @@ use arc_util::{
 };
 use arcdps::{
     exports::{self, CoreColor},
-    imgui::{TabBar, TabItem, TableColumnFlags, TableFlags, TableSortDirection, Ui},
-    Profession,
+    extras::UserRole,
+    imgui::{MenuItem, TabBar, TabItem, TableColumnFlags, TableFlags, TableSortDirection, Ui},
+    Profession, Specialization,
 };
+use std::{cmp::Ordering, iter};
 
 pub type Props<'p> = (&'p Definitions, &'p [CustomReminder]);
 
@@ -71,17 +76,20 @@ impl Tracker {
     }
 
     /// Renders a player entry in a table.
+    #[allow(clippy::too_many_arguments)]
     fn render_table_entry(
         ui: &Ui,
         (defs, custom): Props,
         settings: &TrackerSettings,
+        builds: &Builds,
         colors: &exports::Colors,
         entry: TableEntry,
-        show_sub: bool,
+        columns: TableColumns,
         allow_delete: bool,
-    ) -> bool {
-        let mut delete = false;
+    ) -> EntryAction {
+        let mut action = EntryAction::None;
         let TableEntry { buffs, .. } = entry;
+        let build = builds.assigned(entry.account, entry.character);
         let sub_color = colors
             .sub_base(entry.subgroup)
             .map(|color| render::with_alpha(color, 1.0));
@@ -91,12 +99,13 @@ impl Tracker {
         let red = colors.core(CoreColor::LightRed).unwrap_or(RED);
         let green = colors.core(CoreColor::LightGreen).unwrap_or(GREEN);
         let yellow = colors.core(CoreColor::LightYellow).unwrap_or(YELLOW);
+        let grey = colors.core(CoreColor::MediumGrey).unwrap_or(GREY);
 
         // new row for each player
         ui.table_next_row();
 
         // render subgroup cell
-        if show_sub {
+        if columns.sub {
             ui.table_next_column();
             let sub = format!("{:>2}", entry.subgroup);
             match (settings.color_sub, sub_color, prof_color) {
@@ -109,12 +118,24 @@ impl Tracker {
         // render name cell
         ui.table_next_column();
         match (settings.color_name, sub_color, prof_color) {
+            _ if entry.placeholder => ui.text_colored(grey, entry.character),
             (Color::Sub, Some(color), _) => ui.text_colored(color, entry.character),
             (Color::Prof, _, Some(color)) => ui.text_colored(color, entry.character),
             _ => ui.text(entry.character),
         }
         if ui.is_item_hovered() {
-            ui.tooltip_text(entry.account);
+            ui.tooltip(|| {
+                ui.text(entry.account);
+                if let Some(role) = entry.role {
+                    ui.text(role_name(role));
+                }
+                if let Some(build) = build {
+                    ui.text(format!("Build: {}", build.name));
+                }
+                if entry.placeholder {
+                    ui.text_colored(grey, "Not in range");
+                }
+            });
         }
         render::item_context_menu(format!("name-context-{}", entry.id), || {
             let title = "Entry options";
@@ -132,10 +153,89 @@ impl Tracker {
                 buffs.reset_buffs();
             }
             if allow_delete && ui.small_button("Delete entry") {
-                delete = true;
+                action = EntryAction::Delete;
             }
+            ui.menu("Assign build to account", || {
+                let assigned = builds.accounts.get(entry.account);
+                if MenuItem::new("None").selected(assigned.is_none()).build(ui) {
+                    action = EntryAction::AssignAccount(None);
+                }
+                for (option, library) in builds.all().filter(|(build, _)| {
+                    entry.profession == Profession::Unknown || build.prof == entry.profession
+                }) {
+                    let reference = Builds::reference(option, library);
+                    let selected = assigned == Some(&reference);
+                    if MenuItem::new(&option.name).selected(selected).build(ui) {
+                        action = EntryAction::AssignAccount(Some(reference));
+                    }
+                }
+            });
         });
 
+        // render specialization cell
+        if columns.spec {
+            ui.table_next_column();
+            let spec = match settings.spec_display {
+                SpecDisplay::Name => spec_name(entry.profession, entry.elite),
+                _ => spec_tag(entry.profession, entry.elite),
+            };
+            match (settings.color_name, sub_color, prof_color) {
+                _ if entry.profession == Profession::Unknown => ui.text(""),
+                (Color::Sub, Some(color), _) => ui.text_colored(color, spec),
+                (Color::Prof, _, Some(color)) => ui.text_colored(color, spec),
+                _ => ui.text(spec),
+            }
+            if ui.is_item_hovered() && entry.profession != Profession::Unknown {
+                ui.tooltip_text(spec_name(entry.profession, entry.elite));
+            }
+        }
+
+        // render ready cell
+        if columns.ready {
+            ui.table_next_column();
+            match entry.ready {
+                Some(true) => ui.text_colored(green, "Ready"),
+                Some(false) => ui.text_colored(grey, "-"),
+                None => {}
+            }
+        }
+
+        // render build cell
+        if columns.build {
+            ui.table_next_column();
+            ui.set_next_item_width(-1.0);
+            let mut current = builds.characters.get(entry.character).cloned();
+            let options = builds
+                .all()
+                .filter(|(build, _)| build.prof == entry.profession)
+                .map(|(build, library)| Some(Builds::reference(build, library)));
+            if render_combo(
+                ui,
+                format!("##build-{}", entry.id),
+                iter::once(None).chain(options),
+                &mut current,
+                |reference| match reference
+                    .as_ref()
+                    .and_then(|reference| builds.find(reference))
+                {
+                    Some(build) => build.name.clone().into(),
+                    None => "None".into(),
+                },
+                |_| None,
+            ) {
+                action = EntryAction::AssignCharacter(current);
+            }
+        }
+
+        // color buffs by compliance with the assigned build
+        let compliance_color = |compliance: Option<Compliance>, fallback: [f32; 4]| match compliance
+        {
+            Some(Compliance::Matches) => green,
+            Some(Compliance::Wrong) => yellow,
+            Some(Compliance::Missing) => red,
+            Some(Compliance::Unknown) | None => fallback,
+        };
+
         // render food cell
         ui.table_next_column();
         match buffs.food.state {
@@ -153,10 +253,13 @@ impl Tracker {
             }
             BuffState::Some(buff_id) => {
                 if let Some(DefinitionKind::Food(food)) = defs.definition(buff_id) {
-                    let color = match food.id {
-                        MALNOURISHED => red,
-                        _ => green,
-                    };
+                    let color = compliance_color(
+                        build.map(|build| build.food_compliance(defs, buffs.food.state)),
+                        match food.id {
+                            MALNOURISHED => red,
+                            _ => green,
+                        },
+                    );
                     ui.text_colored(color, &food.display);
                     buff_ui::render_buff_tooltip(ui, food);
                     buff_ui::render_food_context_menu(
@@ -193,10 +296,13 @@ impl Tracker {
             }
             BuffState::Some(buff_id) => {
                 if let Some(DefinitionKind::Util(util)) = defs.definition(buff_id) {
-                    let color = match util.id {
-                        DIMINISHED => red,
-                        _ => green,
-                    };
+                    let color = compliance_color(
+                        build.map(|build| build.util_compliance(defs, buffs.util.state)),
+                        match util.id {
+                            DIMINISHED => red,
+                            _ => green,
+                        },
+                    );
                     ui.text_colored(color, &util.display);
                     buff_ui::render_buff_tooltip(ui, util);
                     buff_ui::render_util_context_menu(
@@ -242,54 +348,112 @@ impl Tracker {
             });
         }
 
-        delete
+        action
+    }
+
+    /// Performs an action on a table entry.
+    fn perform_entry_action(&mut self, account: &str, character: &str, action: EntryAction) {
+        match action {
+            EntryAction::None => {}
+            EntryAction::Delete => self.players.remove_cache_entry(character.to_string()),
+            EntryAction::AssignCharacter(build) => self.builds.assign_character(character, build),
+            EntryAction::AssignAccount(build) => self.builds.assign_account(account, build),
+        }
     }
 
     /// Renders the tracker tab for the squad.
     fn render_squad_tab(&mut self, ui: &Ui, props: Props) {
-        if self.players.is_empty() {
+        // squad members not tracked by arcdps
+        let untracked = if self.settings.show_untracked {
+            self.untracked_members()
+        } else {
+            Vec::new()
+        };
+
+        if self.players.is_empty() && untracked.is_empty() {
             ui.text("No players in range");
         } else {
             let show_sub = self.settings.show_sub;
-            let columns = [
-                TableIconColumn::with_flags(
-                    "Sub",
-                    None,
-                    TableColumnFlags::PREFER_SORT_DESCENDING | TableColumnFlags::DEFAULT_SORT,
-                ),
+            let show_spec = self.settings.spec_display != SpecDisplay::None;
+            let show_ready = self.settings.show_ready;
+            let table_columns = TableColumns {
+                sub: show_sub,
+                spec: show_spec,
+                ready: show_ready,
+                build: false,
+            };
+
+            // collect visible columns with their sorting
+            let mut columns = Vec::new();
+            if show_sub {
+                columns.push((
+                    TableIconColumn::with_flags(
+                        "Sub",
+                        None,
+                        TableColumnFlags::PREFER_SORT_DESCENDING | TableColumnFlags::DEFAULT_SORT,
+                    ),
+                    Some(Sorting::Sub),
+                ));
+            }
+            columns.push((
                 TableIconColumn::with_flags(
                     "Player",
                     None,
                     TableColumnFlags::PREFER_SORT_DESCENDING,
                 ),
+                Some(Sorting::Name),
+            ));
+            if show_spec {
+                columns.push((
+                    TableIconColumn::with_flags("Spec", None, TableColumnFlags::NO_SORT),
+                    None,
+                ));
+            }
+            if show_ready {
+                columns.push((
+                    TableIconColumn::with_flags("Ready", None, TableColumnFlags::NO_SORT),
+                    None,
+                ));
+            }
+            columns.push((
                 TableIconColumn::with_flags(
                     "Food",
                     FOOD_ICON.as_ref(),
                     TableColumnFlags::PREFER_SORT_DESCENDING,
                 ),
+                Some(Sorting::Food),
+            ));
+            columns.push((
                 TableIconColumn::with_flags(
                     "Util",
                     UTIL_ICON.as_ref(),
                     TableColumnFlags::PREFER_SORT_DESCENDING,
                 ),
+                Some(Sorting::Util),
+            ));
+            columns.push((
                 TableIconColumn::with_flags(
                     "Buffs",
                     UNKNOWN_ICON.as_ref(),
                     TableColumnFlags::NO_SORT,
                 ),
-            ];
-            let columns = if show_sub { &columns } else { &columns[1..] };
+                None,
+            ));
+            let (columns, sortings): (Vec<_>, Vec<_>) = columns.into_iter().unzip();
 
             if let Some(_table) = render::table_with_icons_sizing(
                 ui,
                 "##squad-table",
-                columns,
+                &columns,
                 TableFlags::SIZING_STRETCH_PROP
                     | TableFlags::PAD_OUTER_X
                     | TableFlags::SORTABLE
                     | TableFlags::SCROLL_Y,
                 self.settings.show_icons,
-                [0.0, self.table_height(ui, self.players.len())],
+                [
+                    0.0,
+                    self.table_height(ui, self.players.len() + untracked.len()),
+                ],
                 0.0,
             ) {
                 // update sorting if necessary
@@ -297,17 +461,10 @@ impl Tracker {
                     sort_specs.conditional_sort(|column_specs| {
                         let column = column_specs.iter().next().unwrap();
                         if let Some(dir) = column.sort_direction() {
-                            // increase index by 1 if no sub column
-                            let index = column.column_idx() + if show_sub { 0 } else { 1 };
-
                             // update sorting state
-                            self.sorting = match index {
-                                0 => Sorting::Sub,
-                                1 => Sorting::Name,
-                                2 => Sorting::Food,
-                                3 => Sorting::Util,
-                                _ => unreachable!("column sort spec index out of range"),
-                            };
+                            if let Some(Some(sorting)) = sortings.get(column.column_idx()) {
+                                self.sorting = *sorting;
+                            }
 
                             // ascending is reverse order for us
                             self.reverse = dir == TableSortDirection::Ascending;
@@ -318,18 +475,95 @@ impl Tracker {
                     });
                 }
 
+                // placeholders for untracked squad members are merged into the sorted rows
+                let (sorting, reverse) = (self.sorting, self.reverse);
+                let mut placeholders = untracked
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (account, member))| {
+                        let key = RowKey::untracked(account, member);
+                        let index = self
+                            .players
+                            .iter()
+                            .position(|entry| {
+                                sorting.compare(reverse, &key, &RowKey::from_entry(entry))
+                                    == Ordering::Less
+                            })
+                            .unwrap_or(self.players.len());
+                        (index, key, i, account, member)
+                    })
+                    .collect::<Vec<_>>();
+                placeholders.sort_by(|(a_index, a, ..), (b_index, b, ..)| {
+                    a_index
+                        .cmp(b_index)
+                        .then_with(|| sorting.compare(reverse, a, b))
+                });
+                let mut placeholders = placeholders.into_iter().peekable();
+
                 // render table content
                 let colors = exports::colors();
-                for entry in self.players.iter_mut() {
-                    Self::render_table_entry(
+                let mut pending = None;
+                let render_placeholder = |i: usize, account: &String, member: &SquadMember| {
+                    let mut buffs = Buffs::new();
+                    let action = Self::render_table_entry(
                         ui,
                         props,
                         &self.settings,
+                        &self.builds,
                         &colors,
-                        TableEntry::from_entry(entry.player.id, entry),
-                        show_sub,
+                        TableEntry {
+                            id: usize::MAX - 1 - i,
+                            account,
+                            character: account,
+                            profession: Profession::Unknown,
+                            elite: Specialization::Unknown,
+                            subgroup: member.subgroup,
+                            role: Some(member.role),
+                            ready: Some(member.ready),
+                            placeholder: true,
+                            buffs: &mut buffs,
+                        },
+                        table_columns,
                         false,
                     );
+                    (action != EntryAction::None)
+                        .then(|| (account.clone(), account.clone(), action))
+                };
+                for (index, entry) in self.players.iter_mut().enumerate() {
+                    while let Some((_, _, i, account, member)) =
+                        placeholders.next_if(|(at, ..)| *at == index)
+                    {
+                        pending = render_placeholder(i, account, member).or(pending);
+                    }
+
+                    let member = self.squad.get(&entry.player.account);
+                    let action = Self::render_table_entry(
+                        ui,
+                        props,
+                        &self.settings,
+                        &self.builds,
+                        &colors,
+                        TableEntry {
+                            role: member.map(|member| member.role),
+                            ready: member.map(|member| member.ready),
+                            ..TableEntry::from_entry(entry.player.id, entry)
+                        },
+                        table_columns,
+                        false,
+                    );
+                    if action != EntryAction::None {
+                        let Player {
+                            account, character, ..
+                        } = &entry.player;
+                        pending = Some((account.clone(), character.clone(), action));
+                    }
+                }
+                for (_, _, i, account, member) in placeholders {
+                    pending = render_placeholder(i, account, member).or(pending);
+                }
+
+                if let Some((account, character, action)) = pending {
+                    self.perform_entry_action(&account, &character, action);
                 }
             }
         }
@@ -337,7 +571,7 @@ impl Tracker {
 
     /// Renders the tracker tab for own characters.
     fn render_characters_tab(&mut self, ui: &Ui, props: Props) {
-        let current = self.players.get_self();
+        let current = self.self_entry();
         let count = self.players.cache_len() + if current.is_some() { 1 } else { 0 };
 
         if current.is_none() && !self.players.cached() {
@@ -347,6 +581,7 @@ impl Tracker {
             "##self-table",
             &[
                 TableIconColumn::new("Player", None),
+                TableIconColumn::new("Build", None),
                 TableIconColumn::new("Food", FOOD_ICON.as_ref()),
                 TableIconColumn::new("Util", UTIL_ICON.as_ref()),
                 TableIconColumn::new("Buffs", UNKNOWN_ICON.as_ref()),
@@ -358,58 +593,74 @@ impl Tracker {
         ) {
             // render table content
             let colors = exports::colors();
-            if let Some(entry) = self.players.get_self_mut() {
-                Self::render_table_entry(
+            let table_columns = TableColumns {
+                build: true,
+                ..TableColumns::default()
+            };
+            let mut pending = None;
+            if let Some(entry) = Self::find_self_mut(&mut self.players, self.account.as_deref()) {
+                let action = Self::render_table_entry(
                     ui,
                     props,
                     &self.settings,
+                    &self.builds,
                     &colors,
                     TableEntry::from_entry(usize::MAX, entry),
-                    false,
+                    table_columns,
                     false,
                 );
+                if action != EntryAction::None {
+                    let Player {
+                        account, character, ..
+                    } = &entry.player;
+                    pending = Some((account.clone(), character.clone(), action));
+                }
             }
-            let mut delete = None;
             for (i, (player, buffs)) in self.players.cache_iter_mut().enumerate() {
-                let should_delete = Self::render_table_entry(
+                let action = Self::render_table_entry(
                     ui,
                     props,
                     &self.settings,
+                    &self.builds,
                     &colors,
                     TableEntry {
                         id: i,
                         account: &player.account,
                         character: &player.character,
                         profession: player.profession,
+                        elite: Specialization::Unknown,
                         buffs,
                         subgroup: 0,
+                        role: None,
+                        ready: None,
+                        placeholder: false,
                     },
-                    false,
+                    table_columns,
                     true,
                 );
-                if should_delete {
-                    delete = Some(player.character.clone());
+                if action != EntryAction::None {
+                    pending = Some((player.account.clone(), player.character.clone(), action));
                 }
             }
-            if let Some(name) = delete {
-                self.players.remove_cache_entry(name);
+            if let Some((account, character, action)) = pending {
+                self.perform_entry_action(&account, &character, action);
             }
         }
     }
 
     /// Renders the builds tab for user-defined builds.
-    fn render_builds_tab(&mut self, ui: &Ui, (defs, ..): Props) {
-        let current = self.players.get_self();
-        let prof = current.map(|entry| entry.player.profession);
-        let food = current
-            .map(|entry| entry.data.food.state)
-            .unwrap_or(BuffState::Unknown);
-        let util = current
-            .map(|entry| entry.data.util.state)
-            .unwrap_or(BuffState::Unknown);
+    fn render_builds_tab(&mut self, ui: &Ui, (defs, custom): Props) {
+        let entry = self.self_entry().cloned();
+        let current = entry.as_ref().map(|Entry { player, data }| Current {
+            account: &player.account,
+            character: &player.character,
+            prof: player.profession,
+            spec: player.elite,
+            buffs: data,
+        });
 
         self.builds
-            .render(ui, (defs, prof, food, util, self.settings.show_icons));
+            .render(ui, (defs, custom, current, self.settings.show_icons));
     }
 }
 
@@ -434,7 +685,7 @@ impl Component<Props<'_>> for Tracker {
 impl Windowable<Props<'_>> for Tracker {
     const CONTEXT_MENU: bool = true;
 
-    fn render_menu(&mut self, ui: &Ui, _: &mut (&Definitions, &[CustomReminder])) {
+    fn render_menu(&mut self, ui: &Ui, (defs, custom): &mut (&Definitions, &[CustomReminder])) {
         let colors = exports::colors();
         let grey = colors
             .core(CoreColor::MediumGrey)
@@ -457,6 +708,11 @@ impl Windowable<Props<'_>> for Tracker {
             // table column checkboxes
             ui.checkbox("Show icons", &mut self.settings.show_icons);
             ui.checkbox("Show subgroup", &mut self.settings.show_sub);
+            ui.checkbox("Show ready status", &mut self.settings.show_ready);
+            ui.checkbox(
+                "Show players out of range",
+                &mut self.settings.show_untracked,
+            );
             ui.checkbox("Show build notes", &mut self.builds.display_notes);
 
             let input_width = render::ch_width(ui, 16);
@@ -472,6 +728,82 @@ impl Windowable<Props<'_>> for Tracker {
 
             ui.set_next_item_width(input_width);
             render_enum_combo(ui, "Name color", &mut self.settings.color_name);
+
+            ui.set_next_item_width(input_width);
+            render_enum_combo(ui, "Specialization", &mut self.settings.spec_display);
+        });
+
+        // export options
+        ui.menu("Export", || {
+            ui.text_colored(grey, "Export squad");
+
+            ui.set_next_item_width(render::ch_width(ui, 16));
+            render_enum_combo(ui, "Format", &mut self.settings.export_format);
+
+            let format = self.settings.export_format;
+            if ui.button("Copy to clipboard") {
+                let rows = self.export_rows(defs, custom);
+                ui.set_clipboard_text(export::export(custom, &rows, format));
+            }
+            ui.same_line();
+            if ui.button("Save to file") {
+                let rows = self.export_rows(defs, custom);
+                self.export_to_file(format, &export::export(custom, &rows, format));
+            }
+
+            match &self.export_result {
+                None => {}
+                Some(Ok(path)) => ui.text_colored(grey, format!("Saved to \"{}\"", path.display())),
+                Some(Err(_)) => ui.text_colored(
+                    colors.core(CoreColor::LightRed).unwrap_or(RED),
+                    "Failed to save file",
+                ),
+            }
+        });
+
+        // chat callout
+        ui.menu("Callout", || {
+            ui.text_colored(grey, "Missing consumables callout");
+
+            if ui.checkbox("Use account names", &mut self.settings.callout_accounts) {
+                self.callout = None;
+            }
+
+            if ui.button("Copy callout") {
+                let mut callout = self.create_callout();
+                if let Some(chunk) = callout.advance() {
+                    ui.set_clipboard_text(chunk);
+                }
+                self.callout = Some(callout);
+            }
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Only includes players tracked by arcdps.\nSquad members out of range are not included.");
+            }
+
+            match &mut self.callout {
+                None => {}
+                Some(callout) if callout.chunks.is_empty() => {
+                    ui.text_colored(grey, "Nobody is missing consumables")
+                }
+                Some(callout) if callout.is_done() => {
+                    let len = callout.chunks.len();
+                    ui.text_colored(grey, format!("Copied {len}/{len}"))
+                }
+                Some(callout) => {
+                    let label =
+                        format!("Copy next ({}/{})", callout.next + 1, callout.chunks.len());
+                    if ui.button(label) {
+                        if let Some(chunk) = callout.advance() {
+                            ui.set_clipboard_text(chunk);
+                        }
+                    }
+                    if ui.is_item_hovered() {
+                        if let Some(chunk) = callout.chunks.get(callout.next) {
+                            ui.tooltip_text(chunk);
+                        }
+                    }
+                }
+            }
         });
     }
 }
@@ -482,7 +814,11 @@ struct TableEntry<'a> {
     account: &'a str,
     character: &'a str,
     profession: Profession,
+    elite: Specialization,
     subgroup: usize,
+    role: Option<UserRole>,
+    ready: Option<bool>,
+    placeholder: bool,
     buffs: &'a mut Buffs,
 }
 
@@ -493,8 +829,30 @@ impl<'a> TableEntry<'a> {
             account: &entry.player.account,
             character: &entry.player.character,
             profession: entry.player.profession,
+            elite: entry.player.elite,
             subgroup: entry.player.subgroup,
+            role: None,
+            ready: None,
+            placeholder: false,
             buffs: &mut entry.data,
         }
     }
 }
+
+/// Optional columns of a player table.
+#[derive(Debug, Default, Clone, Copy)]
+struct TableColumns {
+    sub: bool,
+    spec: bool,
+    ready: bool,
+    build: bool,
+}
+
+/// Action performed on a player table entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum EntryAction {
+    None,
+    Delete,
+    AssignCharacter(Option<BuildRef>),
+    AssignAccount(Option<BuildRef>),
+}